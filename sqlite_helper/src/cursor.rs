@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::Error;
+
+/// A single column value stored in a [`Cursor`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CursorValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// An opaque token pointing just past the last row of a page, returned by `page_after`.
+///
+/// The token is URL safe and can be handed to clients as is, it round trips through
+/// `to_string` and `Cursor::from`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor(String);
+
+/// A page of rows and the cursor for the next one, `None` once the last row has been returned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub rows: Vec<T>,
+    pub next_cursor: Option<Cursor>,
+}
+
+impl Cursor {
+    /// Each value is a type tag followed by its bytes in hex, values are separated by `.`.
+    pub fn encode(values: &[CursorValue]) -> Cursor {
+        let parts: Vec<String> = values.iter().map(|value| match value {
+            CursorValue::Null => String::from("n"),
+            CursorValue::Integer(integer) => format!("i{}", hex(&integer.to_be_bytes())),
+            CursorValue::Real(real) => format!("r{}", hex(&real.to_bits().to_be_bytes())),
+            CursorValue::Text(text) => format!("t{}", hex(text.as_bytes())),
+            CursorValue::Blob(blob) => format!("b{}", hex(blob)),
+        }).collect();
+        Cursor(parts.join("."))
+    }
+
+    pub fn decode(&self) -> Result<Vec<CursorValue>, Error> {
+        if self.0.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.0.split('.').map(|part| {
+            // Tokens come back from clients, so the tag may be any character.
+            let mut chars = part.chars();
+            let tag = chars.next().ok_or(Error::InvalidCursor)?;
+            let bytes = unhex(chars.as_str())?;
+            match tag {
+                'n' if bytes.is_empty() => Ok(CursorValue::Null),
+                'i' => Ok(CursorValue::Integer(i64::from_be_bytes(eight_bytes(&bytes)?))),
+                'r' => Ok(CursorValue::Real(f64::from_bits(u64::from_be_bytes(eight_bytes(&bytes)?)))),
+                't' => String::from_utf8(bytes).map(CursorValue::Text).map_err(|_| Error::InvalidCursor),
+                'b' => Ok(CursorValue::Blob(bytes)),
+                _ => Err(Error::InvalidCursor),
+            }
+        }).collect()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(token: String) -> Cursor {
+        Cursor(token)
+    }
+}
+
+impl From<&str> for Cursor {
+    fn from(token: &str) -> Cursor {
+        Cursor(token.to_string())
+    }
+}
+
+/// The condition selecting the rows that come after `values` when ordering by `columns` ascending, with NULLs sorting
+/// first like they do in SQLite. A row value comparison such as `(a, b) > (?, ?)` is NULL as soon as one side holds a
+/// NULL, so each column is compared on its own. Returns the condition and, for each `?` in it, the index of its value.
+pub fn after_condition(columns: &[&str], values: &[CursorValue]) -> (String, Vec<usize>) {
+    let mut alternatives: Vec<String> = Vec::new();
+    let mut params: Vec<usize> = Vec::new();
+    for (index, column) in columns.iter().enumerate().take(values.len()) {
+        let mut terms: Vec<String> = Vec::new();
+        for (previous, value) in values[..index].iter().enumerate() {
+            if *value == CursorValue::Null {
+                terms.push(format!("{} IS NULL", columns[previous]));
+            } else {
+                terms.push(format!("{} = ?", columns[previous]));
+                params.push(previous);
+            }
+        }
+        if values[index] == CursorValue::Null {
+            terms.push(format!("{} IS NOT NULL", column));
+        } else {
+            terms.push(format!("{} > ?", column));
+            params.push(index);
+        }
+        alternatives.push(format!("({})", terms.join(" AND ")));
+    }
+    (format!("({})", alternatives.join(" OR ")), params)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(text: &str) -> Result<Vec<u8>, Error> {
+    text.as_bytes().chunks(2).map(|pair| {
+        let pair = std::str::from_utf8(pair).map_err(|_| Error::InvalidCursor)?;
+        if pair.len() != 2 {
+            return Err(Error::InvalidCursor);
+        }
+        u8::from_str_radix(pair, 16).map_err(|_| Error::InvalidCursor)
+    }).collect()
+}
+
+fn eight_bytes(bytes: &[u8]) -> Result<[u8; 8], Error> {
+    let mut array = [0; 8];
+    if bytes.len() != array.len() {
+        return Err(Error::InvalidCursor);
+    }
+    array.copy_from_slice(bytes);
+    Ok(array)
+}
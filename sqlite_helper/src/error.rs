@@ -0,0 +1,165 @@
+use std::fmt;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The error returned by every generated function.
+///
+/// Driver errors are converted with `From`, constraint violations and failed conversions get their own
+/// variants so they can be matched on, anything else is kept as the driver's error in `Driver`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// `get` found no row matching the filter.
+    NotFound,
+    /// `get` or `get_optional` found more than one row matching the filter.
+    NotUnique,
+    /// A row broke a constraint of the table.
+    Constraint {
+        kind: ConstraintKind,
+        /// The table SQLite named in the message, if any.
+        table: Option<String>,
+        /// The columns SQLite named in the message, empty for `CHECK` and `FOREIGN KEY` violations.
+        columns: Vec<String>,
+        message: String,
+    },
+    /// A value couldn't be converted between its SQLite and rust types.
+    Conversion(Box<dyn std::error::Error + Send + Sync>),
+    /// A `Cursor` that wasn't produced by `page_after` for the same columns.
+    InvalidCursor,
+    /// A generated function was called with arguments it can't do anything with, like an empty patch.
+    InvalidArgument(String),
+    /// Any other error from rusqlite or libsql_client.
+    Driver(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConstraintKind {
+    Unique,
+    PrimaryKey,
+    ForeignKey,
+    Check,
+    NotNull,
+    Other,
+}
+
+impl Error {
+    pub fn conversion(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Error {
+        Error::Conversion(err.into())
+    }
+
+    /// Builds an `Error::Constraint` from an SQLite message like `UNIQUE constraint failed: users.email`,
+    /// `None` when the message isn't about a constraint.
+    pub fn from_constraint_message(message: &str) -> Option<Error> {
+        let start = message.find(" constraint failed")?;
+        let kind_start = message[..start].rfind(|c: char| !(c.is_ascii_uppercase() || c == ' ')).map_or(0, |index| index + 1);
+        let kind = match message[kind_start..start].trim() {
+            "UNIQUE" => ConstraintKind::Unique,
+            "PRIMARY KEY" => ConstraintKind::PrimaryKey,
+            "FOREIGN KEY" => ConstraintKind::ForeignKey,
+            "CHECK" => ConstraintKind::Check,
+            "NOT NULL" => ConstraintKind::NotNull,
+            _ => ConstraintKind::Other,
+        };
+
+        let mut table = None;
+        let mut columns = Vec::new();
+        let named = message[start..].split_once(": ").map(|(_, named)| named);
+        if let (Some(named), ConstraintKind::Unique | ConstraintKind::PrimaryKey | ConstraintKind::NotNull) = (named, kind) {
+            for name in named.split(", ") {
+                match name.trim().rsplit_once('.') {
+                    Some((table_name, column)) => {
+                        table = Some(table_name.to_string());
+                        columns.push(column.to_string());
+                    },
+                    None => columns.push(name.trim().to_string()),
+                }
+            }
+        }
+
+        Some(Error::Constraint { kind, table, columns, message: message.to_string() })
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotFound => write!(f, "no row matches the filter"),
+            Error::NotUnique => write!(f, "more than one row matches the filter"),
+            Error::Constraint { message, .. } => write!(f, "{}", message),
+            Error::Conversion(err) => write!(f, "conversion failed: {}", err),
+            Error::InvalidCursor => write!(f, "invalid pagination cursor"),
+            Error::InvalidArgument(message) => write!(f, "{}", message),
+            Error::Driver(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Conversion(err) | Error::Driver(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::num::TryFromIntError> for Error {
+    fn from(err: std::num::TryFromIntError) -> Error {
+        Error::conversion(err)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for Error {
+    fn from(err: std::string::FromUtf8Error) -> Error {
+        Error::conversion(err)
+    }
+}
+
+impl From<std::convert::Infallible> for Error {
+    fn from(err: std::convert::Infallible) -> Error {
+        match err {}
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Error {
+        match err {
+            rusqlite::Error::QueryReturnedNoRows => Error::NotFound,
+            rusqlite::Error::SqliteFailure(failure, Some(message)) if failure.code == rusqlite::ErrorCode::ConstraintViolation => {
+                match Error::from_constraint_message(&message) {
+                    // SQLite reports primary key violations as UNIQUE, the extended code tells them apart.
+                    Some(Error::Constraint { kind: ConstraintKind::Unique, table, columns, message }) if failure.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY => {
+                        Error::Constraint { kind: ConstraintKind::PrimaryKey, table, columns, message }
+                    },
+                    Some(constraint) => constraint,
+                    None => Error::Driver(Box::new(rusqlite::Error::SqliteFailure(failure, Some(message)))),
+                }
+            },
+            rusqlite::Error::FromSqlConversionFailure(..)
+            | rusqlite::Error::IntegralValueOutOfRange(..)
+            | rusqlite::Error::InvalidColumnType(..)
+            | rusqlite::Error::Utf8Error(..) => Error::conversion(err),
+            err => Error::Driver(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+impl From<rusqlite::types::FromSqlError> for Error {
+    fn from(err: rusqlite::types::FromSqlError) -> Error {
+        Error::conversion(err)
+    }
+}
+
+/// libsql_client reports its errors as `anyhow::Error`, the constraint message can be anywhere in its chain.
+#[cfg(feature = "libsql")]
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Error {
+        match err.chain().find_map(|cause| Error::from_constraint_message(&cause.to_string())) {
+            Some(constraint) => constraint,
+            None => Error::Driver(err.into()),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/// A handle the `Queryable` functions can run their statements on.
+///
+/// Implemented for a `Connection`, a `Transaction` or `Savepoint` on one, and with the `r2d2` feature for
+/// connections taken from an r2d2 pool, so a batch of calls can be run inside a transaction explicitly:
+///
+/// ```ignore
+/// let tx = conn.transaction()?;
+/// user.add(&tx)?;
+/// other_user.add(&tx)?;
+/// tx.commit()?;
+/// ```
+pub trait Executor {
+    fn connection(&self) -> &rusqlite::Connection;
+}
+
+impl Executor for rusqlite::Connection {
+    fn connection(&self) -> &rusqlite::Connection {
+        self
+    }
+}
+
+impl Executor for rusqlite::Transaction<'_> {
+    fn connection(&self) -> &rusqlite::Connection {
+        self
+    }
+}
+
+impl Executor for rusqlite::Savepoint<'_> {
+    fn connection(&self) -> &rusqlite::Connection {
+        self
+    }
+}
+
+#[cfg(feature = "r2d2")]
+impl Executor for r2d2::PooledConnection<r2d2_sqlite::SqliteConnectionManager> {
+    fn connection(&self) -> &rusqlite::Connection {
+        self
+    }
+}
@@ -0,0 +1,81 @@
+/// A condition on a single column, used by the fields of a `{Struct}Request`.
+///
+/// For `Option<T>` fields the filter is over `T`, `IsNull` and `IsNotNull` cover the `None` case.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter<T> {
+    /// `column = value`
+    Eq(T),
+    /// `column <> value`
+    Ne(T),
+    /// `column < value`
+    Lt(T),
+    /// `column <= value`
+    Le(T),
+    /// `column > value`
+    Gt(T),
+    /// `column >= value`
+    Ge(T),
+    /// `column LIKE pattern`
+    Like(String),
+    /// `column GLOB pattern`
+    Glob(String),
+    /// `column IN (values...)`, an empty list matches nothing
+    In(Vec<T>),
+    /// `column IS NULL`
+    IsNull,
+    /// `column IS NOT NULL`
+    IsNotNull,
+    /// `column BETWEEN low AND high`, both ends included
+    Between(T, T),
+}
+
+/// A value bound by a [`Filter`], either a value of the column's type or a `LIKE` / `GLOB` pattern.
+#[derive(Debug, PartialEq)]
+pub enum Param<'a, T> {
+    Value(&'a T),
+    Text(&'a String),
+}
+
+impl<T> Filter<T> {
+    /// Renders the condition on the already quoted `column`, using a `?` placeholder
+    /// for every value and pushing those values onto `params` in the same order.
+    pub fn condition<'a>(&'a self, column: &str, params: &mut Vec<Param<'a, T>>) -> String {
+        let (operator, value) = match self {
+            Filter::Eq(value) => ("=", value),
+            Filter::Ne(value) => ("<>", value),
+            Filter::Lt(value) => ("<", value),
+            Filter::Le(value) => ("<=", value),
+            Filter::Gt(value) => (">", value),
+            Filter::Ge(value) => (">=", value),
+            Filter::Like(pattern) => {
+                params.push(Param::Text(pattern));
+                return format!("{} LIKE ?", column);
+            },
+            Filter::Glob(pattern) => {
+                params.push(Param::Text(pattern));
+                return format!("{} GLOB ?", column);
+            },
+            Filter::In(values) => {
+                params.extend(values.iter().map(Param::Value));
+                let placeholders: Vec<&str> = values.iter().map(|_| "?").collect();
+                return format!("{} IN ({})", column, placeholders.join(", "));
+            },
+            Filter::IsNull => return format!("{} IS NULL", column),
+            Filter::IsNotNull => return format!("{} IS NOT NULL", column),
+            Filter::Between(low, high) => {
+                params.push(Param::Value(low));
+                params.push(Param::Value(high));
+                return format!("{} BETWEEN ? AND ?", column);
+            },
+        };
+
+        params.push(Param::Value(value));
+        format!("{} {} ?", column, operator)
+    }
+}
+
+impl<T> From<T> for Filter<T> {
+    fn from(value: T) -> Filter<T> {
+        Filter::Eq(value)
+    }
+}
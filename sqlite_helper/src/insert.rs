@@ -0,0 +1,13 @@
+/// The most parameters `add_many` binds in one statement, SQLite before 3.32 refuses statements with more than 999.
+pub const MAX_PARAMETERS: usize = 999;
+
+/// How many rows of `columns` values fit in one statement.
+pub fn rows_per_statement(columns: usize) -> usize {
+    (MAX_PARAMETERS / columns.max(1)).max(1)
+}
+
+/// The `VALUES` list for `rows` rows of `columns` placeholders each, like `(?, ?), (?, ?)`.
+pub fn values_placeholders(rows: usize, columns: usize) -> String {
+    let row = format!("({})", vec!["?"; columns].join(", "));
+    vec![row.as_str(); rows].join(", ")
+}
@@ -0,0 +1,28 @@
+//! Types used by the code that the `database_macros` derives generate.
+
+mod cursor;
+mod error;
+#[cfg(feature = "rusqlite")]
+mod executor;
+mod filter;
+mod insert;
+#[cfg(feature = "libsql")]
+mod libsql;
+mod order;
+
+pub use cursor::{after_condition, Cursor, CursorValue, Page};
+pub use error::{ConstraintKind, Error, Result};
+#[cfg(feature = "rusqlite")]
+pub use executor::Executor;
+pub use filter::{Filter, Param};
+pub use insert::{rows_per_statement, values_placeholders, MAX_PARAMETERS};
+#[cfg(feature = "libsql")]
+pub use libsql::{transaction, LibSqlExecutor};
+pub use order::{Column, Direction, Nulls, OrderBy, QueryOptions};
+
+/// What the `LibSqlQueryable` streams are built with.
+#[cfg(feature = "libsql")]
+pub mod stream {
+    pub use futures_core::Stream;
+    pub use futures_util::stream::unfold;
+}
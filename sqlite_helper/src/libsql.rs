@@ -0,0 +1,66 @@
+use futures_core::future::BoxFuture;
+
+use crate::Error;
+
+/// A handle the `LibSqlQueryable` functions can run their statements on, a `Client` or a `Transaction` on one.
+pub trait LibSqlExecutor {
+    fn execute(&self, statement: libsql_client::Statement) -> BoxFuture<'_, anyhow::Result<libsql_client::ResultSet>>;
+
+    /// Runs `statements` in order, all or none of them on a `Client`. A `Transaction` runs them as part of itself,
+    /// so when one fails the ones before it are only undone by rolling the transaction back.
+    fn batch(&self, statements: Vec<libsql_client::Statement>) -> BoxFuture<'_, anyhow::Result<Vec<libsql_client::ResultSet>>>;
+}
+
+impl LibSqlExecutor for libsql_client::Client {
+    fn execute(&self, statement: libsql_client::Statement) -> BoxFuture<'_, anyhow::Result<libsql_client::ResultSet>> {
+        Box::pin(libsql_client::Client::execute(self, statement))
+    }
+
+    fn batch(&self, statements: Vec<libsql_client::Statement>) -> BoxFuture<'_, anyhow::Result<Vec<libsql_client::ResultSet>>> {
+        Box::pin(libsql_client::Client::batch(self, statements))
+    }
+}
+
+impl LibSqlExecutor for libsql_client::Transaction<'_> {
+    fn execute(&self, statement: libsql_client::Statement) -> BoxFuture<'_, anyhow::Result<libsql_client::ResultSet>> {
+        Box::pin(libsql_client::Transaction::execute(self, statement))
+    }
+
+    fn batch(&self, statements: Vec<libsql_client::Statement>) -> BoxFuture<'_, anyhow::Result<Vec<libsql_client::ResultSet>>> {
+        Box::pin(async move {
+            let mut results = Vec::with_capacity(statements.len());
+            for statement in statements {
+                results.push(libsql_client::Transaction::execute(self, statement).await?);
+            }
+            Ok(results)
+        })
+    }
+}
+
+/// Runs `f` in a transaction, which is committed when `f` returns `Ok` and rolled back when it returns an error.
+///
+/// ```ignore
+/// sqlite_helper::transaction(&client, |tx| Box::pin(async move {
+///     first_user.add(tx).await?;
+///     second_user.add(tx).await?;
+///     Ok::<_, sqlite_helper::Error>(())
+/// })).await?;
+/// ```
+pub async fn transaction<T, E, F>(client: &libsql_client::Client, f: F) -> Result<T, E>
+where
+    F: for<'t> FnOnce(&'t libsql_client::Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+    E: From<Error>,
+{
+    let tx = client.transaction().await.map_err(Error::from)?;
+    match f(&tx).await {
+        Ok(value) => {
+            tx.commit().await.map_err(Error::from)?;
+            Ok(value)
+        },
+        Err(err) => {
+            // The error from `f` is the one worth returning, a failed rollback ends the transaction all the same.
+            let _ = tx.rollback().await;
+            Err(err)
+        },
+    }
+}
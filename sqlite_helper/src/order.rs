@@ -0,0 +1,116 @@
+/// A column that rows can be ordered by, implemented by the generated `{Struct}Field` enums.
+pub trait Column {
+    /// The quoted column name.
+    fn column(&self) -> &'static str;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+/// Where `NULL` values go, SQLite puts them first when ascending and last when descending by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderBy<F> {
+    pub field: F,
+    pub direction: Direction,
+    pub nulls: Option<Nulls>,
+}
+
+/// `ORDER BY`, `LIMIT` and `OFFSET` for `get_many_with`.
+///
+/// ```ignore
+/// let options = QueryOptions::new().desc(UserField::CreatedAt).nulls_last().asc(UserField::UserId).limit(20).offset(40);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryOptions<F> {
+    pub order_by: Vec<OrderBy<F>>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+impl<F> Default for QueryOptions<F> {
+    fn default() -> Self {
+        QueryOptions { order_by: Vec::new(), limit: None, offset: None }
+    }
+}
+
+impl<F: Column> QueryOptions<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn order_by(mut self, field: F, direction: Direction) -> Self {
+        self.order_by.push(OrderBy { field, direction, nulls: None });
+        self
+    }
+
+    pub fn asc(self, field: F) -> Self {
+        self.order_by(field, Direction::Asc)
+    }
+
+    pub fn desc(self, field: F) -> Self {
+        self.order_by(field, Direction::Desc)
+    }
+
+    /// Puts `NULL` values first for the last field added with `order_by`, `asc` or `desc`.
+    pub fn nulls_first(self) -> Self {
+        self.nulls(Nulls::First)
+    }
+
+    /// Puts `NULL` values last for the last field added with `order_by`, `asc` or `desc`.
+    pub fn nulls_last(self) -> Self {
+        self.nulls(Nulls::Last)
+    }
+
+    fn nulls(mut self, nulls: Nulls) -> Self {
+        if let Some(order_by) = self.order_by.last_mut() {
+            order_by.nulls = Some(nulls);
+        }
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Renders the options as the clauses following `WHERE`, an empty string when nothing is set.
+    pub fn sql(&self) -> String {
+        let mut sql = String::new();
+        if !self.order_by.is_empty() {
+            let terms: Vec<String> = self.order_by.iter().map(|order_by| {
+                let direction = match order_by.direction {
+                    Direction::Asc => "ASC",
+                    Direction::Desc => "DESC",
+                };
+                match order_by.nulls {
+                    Some(Nulls::First) => format!("{} {} NULLS FIRST", order_by.field.column(), direction),
+                    Some(Nulls::Last) => format!("{} {} NULLS LAST", order_by.field.column(), direction),
+                    None => format!("{} {}", order_by.field.column(), direction),
+                }
+            }).collect();
+            sql = format!(" ORDER BY {}", terms.join(", "));
+        }
+
+        // SQLite only accepts OFFSET after a LIMIT, -1 means no limit.
+        match (self.limit, self.offset) {
+            (Some(limit), Some(offset)) => format!("{} LIMIT {} OFFSET {}", sql, limit, offset),
+            (Some(limit), None) => format!("{} LIMIT {}", sql, limit),
+            (None, Some(offset)) => format!("{} LIMIT -1 OFFSET {}", sql, offset),
+            (None, None) => sql,
+        }
+    }
+}
@@ -1,38 +1,61 @@
 extern crate proc_macro;
-use quote::quote;
+use quote::{quote, format_ident};
 use proc_macro::TokenStream;
-use syn::{ parse_macro_input, DeriveInput, Field, Data, Fields, FieldsNamed, Ident};
+use syn::{ parse_macro_input, DeriveInput, Field, Data, Fields, FieldsNamed, Ident, Type, GenericArgument, PathArguments, LitStr};
+use syn::ext::IdentExt;
+use syn::parse::Parse;
 
-#[proc_macro_derive(Queryable, attributes(primary))]
+#[proc_macro_derive(Queryable, attributes(primary, unique, table, column))]
 pub fn print_tokens(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = parse_macro_input!(input as DeriveInput);
-    let new_functions: proc_macro2::TokenStream;
-    // Check if the input is a struct
-    if let Data::Struct(data_struct) = ast.data {
-        let struct_name = ast.ident;
-
-        match data_struct.fields {
-            Fields::Named(fields_named) => {
-                let request = request_struct(&fields_named, &struct_name);
-                let get_fn_tokens = body_get(&fields_named, &struct_name);
-                let add_fn_tokens = body_add(&fields_named, &struct_name);
-                let update_fn_tokens = body_update(&fields_named, &struct_name);
-                let delete_fn_tokens = body_delete(&fields_named, &struct_name);
-                new_functions = quote! {
-                    #request
-
-                    impl #struct_name {
-                        #get_fn_tokens
-                        #add_fn_tokens
-                        #update_fn_tokens
-                        #delete_fn_tokens
-                    }
-                }
-            }
-            _ => panic!("Only structs with named fields are supported"),
+    let table = match parse_table(&ast) {
+        Ok(table) => table,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+    let fields_named = match named_fields(&ast, "Queryable") {
+        Ok(fields_named) => fields_named,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+    let struct_name = &ast.ident;
+    let fields: Vec<FieldAttribute> = match check_fields(fields_named, struct_name, &table) {
+        Ok(fields) => fields,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let request = request_struct(&fields, struct_name);
+    let filter = filter_enum(struct_name);
+    let field = field_enum(&fields, struct_name);
+    let where_clause = filter_where_clause(struct_name, quote!(&'a dyn rusqlite::ToSql), request_conditions(&fields));
+    let patch = patch_struct(&fields, struct_name);
+    let get_fn_tokens = body_get(&fields, struct_name, &table);
+    let page_fn_tokens = body_page(&fields, struct_name, &table);
+    let aggregate_fn_tokens = body_aggregate(&fields, struct_name, &table);
+    let add_fn_tokens = body_add(&fields, &table);
+    let update_fn_tokens = body_update(&fields, struct_name, &table);
+    let upsert_fn_tokens = body_upsert(&fields, &table);
+    let delete_fn_tokens = body_delete(&fields, struct_name, &table);
+    let create_table_fn_tokens = body_create_table(&fields, &table);
+    let new_functions = quote! {
+        #request
+        #filter
+        #where_clause
+        #field
+        #patch
+
+        impl #struct_name {
+            #create_table_fn_tokens
+            #get_fn_tokens
+            #page_fn_tokens
+            #aggregate_fn_tokens
+            #add_fn_tokens
+            #upsert_fn_tokens
+            #update_fn_tokens
+            #delete_fn_tokens
         }
-    } else {
-        panic!("Only structs are supported");
+    };
+
+    if let Err(err) = write_debug_output("Queryable", &fields, struct_name, &table, &new_functions) {
+        return TokenStream::from(err.to_compile_error());
     }
     return TokenStream::from(new_functions);
 }
@@ -59,334 +82,2065 @@ pub fn print_binding_tokens(input: TokenStream) -> TokenStream {
             }
         }
     } else {
-        panic!("Only enums are supported");
+        return TokenStream::from(syn::Error::new(ast.ident.span(), "Bindable only supports enums").to_compile_error());
     }
     return TokenStream::from(new_functions);
 }
 
-fn request_struct(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
+/// The named fields of the struct a derive is applied to, the derives don't support anything else.
+fn named_fields<'a>(ast: &'a DeriveInput, derive: &str) -> syn::Result<&'a FieldsNamed> {
+    match &ast.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => Ok(fields_named),
+            Fields::Unnamed(fields_unnamed) => Err(syn::Error::new_spanned(fields_unnamed, format!("{} only supports structs with named fields", derive))),
+            Fields::Unit => Err(syn::Error::new(ast.ident.span(), format!("{} only supports structs with named fields", derive))),
+        },
+        Data::Enum(data_enum) => Err(syn::Error::new(data_enum.enum_token.span, format!("{} only supports structs", derive))),
+        Data::Union(data_union) => Err(syn::Error::new(data_union.union_token.span, format!("{} only supports structs", derive))),
+    }
+}
+
+/// `{Struct}Request` holds a filter per column, `Option<T>` fields are filtered on `T`.
+fn request_struct(fields: &[FieldAttribute], struct_name: &Ident) -> proc_macro2::TokenStream {
     let request_struct: &Ident = &Ident::new(&format!("{}Request", struct_name), proc_macro2::Span::call_site());
-    let idents: Vec<_> = fields_named.named.iter().map(|f| &f.ident).collect();
-    let types: Vec<_> = fields_named.named.iter().map(|f| &f.ty).collect();
+    let idents: Vec<_> = fields.iter().map(|f| f.ident).collect();
+    let types: Vec<&Type> = fields.iter().map(|f| option_inner_type(&f.field.ty).unwrap_or(&f.field.ty)).collect();
     quote! {
         #[derive(Default, Clone)]
         pub struct #request_struct {
-            #(pub #idents : Option<#types>),*
+            #(pub #idents : Option<::sqlite_helper::Filter<#types>>),*
         }
 
     }
 }
 
-fn body_get(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
+/// `{Struct}Filter` combines requests with AND, OR and NOT, it is what get, get_many and the where helpers take.
+fn filter_enum(struct_name: &Ident) -> proc_macro2::TokenStream {
     let request_struct: &Ident = &Ident::new(&format!("{}Request", struct_name), proc_macro2::Span::call_site());
-    let struct_name_string = String::from(struct_name.to_string());
-    let idents: Vec<_> = fields_named.named.iter().map(|f| &f.ident).collect();
-    let index: Vec<_> = fields_named.named.iter().enumerate().map(|f| f.0).collect();
-    let types: Vec<_> = fields_named.named.iter().map(|f| &f.ty).collect();
-    let conditions: Vec<String> = idents.iter().map(|ident| format!("AND {} = ", ident.as_ref().unwrap())).collect();
-
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
     quote! {
-        pub fn get(conn: &rusqlite::Connection, request: #request_struct) -> anyhow::Result<Self> {
-            let mut count = 1;
-            let mut query_string: String = format!("SELECT * FROM {} WHERE TRUE = TRUE", #struct_name_string);
-            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
-            #(
-                let mut #idents: #types;
-                if let Some(i) = request.#idents {
-                    query_string = format!("{}\n{}?{}", query_string, #conditions, count);
-                    #idents = i.clone();
-                    to_sql_objects.push(&#idents);
+        #[derive(Clone)]
+        pub enum #filter_enum {
+            /// Every `Some` filter of the request, an empty request matches every row.
+            Where(#request_struct),
+            /// All of the filters, an empty list matches every row.
+            And(Vec<#filter_enum>),
+            /// Any of the filters, an empty list matches no rows.
+            Or(Vec<#filter_enum>),
+            Not(Box<#filter_enum>),
+        }
 
-                    count += 1;
-                }
-            )*
+        impl #filter_enum {
+            pub fn and(self, other: impl Into<#filter_enum>) -> #filter_enum {
+                #filter_enum::And(vec![self, other.into()])
+            }
 
-            let obj: #struct_name = conn.query_row((&query_string), rusqlite::params_from_iter(to_sql_objects), |row| {
-                Ok(#struct_name {
-                    #(#idents : row.get(#index)?,)*
-                })
-            })?;
+            pub fn or(self, other: impl Into<#filter_enum>) -> #filter_enum {
+                #filter_enum::Or(vec![self, other.into()])
+            }
+        }
 
-            return Ok(obj);
+        impl std::ops::Not for #filter_enum {
+            type Output = #filter_enum;
+
+            fn not(self) -> #filter_enum {
+                #filter_enum::Not(Box::new(self))
+            }
         }
-        
-        pub fn get_many(conn: &rusqlite::Connection, request: #request_struct) -> anyhow::Result<Vec<Self>> {
-            let mut count = 1;
-            let mut query_string: String = format!("SELECT * FROM {} WHERE TRUE = TRUE", #struct_name_string);
-            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
-            #(
-                let mut #idents: #types;
-                if let Some(i) = request.#idents {
-                    query_string = format!("{}\n{}?{}", query_string, #conditions, count);
-                    #idents = i.clone();
-                    to_sql_objects.push(&#idents);
 
-                    count += 1;
-                }
-            )*
-            let mut stmt = conn.prepare(&query_string)?;
-            let obj_iter = stmt.query_map(rusqlite::params_from_iter(to_sql_objects), |row| {
-                Ok(#struct_name {
-                    #(#idents : row.get(#index)?,)*
-                })
-            })?;
+        impl From<#request_struct> for #filter_enum {
+            fn from(request: #request_struct) -> #filter_enum {
+                #filter_enum::Where(request)
+            }
+        }
 
-            let obj_vector = obj_iter.map(|x| x.unwrap()).collect();
+        impl #request_struct {
+            pub fn and(self, other: impl Into<#filter_enum>) -> #filter_enum {
+                #filter_enum::from(self).and(other)
+            }
 
-            return Ok(obj_vector);
+            pub fn or(self, other: impl Into<#filter_enum>) -> #filter_enum {
+                #filter_enum::from(self).or(other)
+            }
+        }
+
+        impl std::ops::Not for #request_struct {
+            type Output = #filter_enum;
 
+            fn not(self) -> #filter_enum {
+                !#filter_enum::from(self)
+            }
         }
     }
 }
 
-fn body_add(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
-    let struct_name_string = String::from(struct_name.to_string());
-    let idents: Vec<_> = fields_named.named.iter().map(|f| &f.ident).collect();
-    
-    let vals: Vec<String> = idents.iter()
-        .enumerate()
-        .map(|(i, _)| format!("?{}", i + 1)).collect();
-    let joined_vals = vals.join(", ");
-
-    let var_strings: Vec<_> = idents.iter().filter_map(|&opt| opt.as_ref()).map(|ident| ident.to_string()).collect();
-    let joined_vars: String = var_strings.join(", ");
-    let query_string: String = format!("INSERT INTO {} ({}) VALUES ({});", struct_name_string, joined_vars, joined_vals);
-    
-     
+/// `{Struct}Field` names a column to order by, with a variant per field in PascalCase.
+fn field_enum(fields: &[FieldAttribute], struct_name: &Ident) -> proc_macro2::TokenStream {
+    let field_enum: &Ident = &format_ident!("{}Field", struct_name);
+    let variants: Vec<Ident> = fields.iter().map(field_variant).collect();
+    let columns: Vec<String> = fields.iter().map(|f| quote_identifier(&f.column_name)).collect();
     quote! {
-        pub fn add(&self, conn: &rusqlite::Connection) -> anyhow::Result<usize> {
-            let query_string: &str = #query_string;
-            let stmt: usize = conn.execute(query_string, rusqlite::params! [#( self.#idents),*])?;  
-            return Ok(stmt);
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum #field_enum {
+            #(#variants),*
+        }
+
+        impl ::sqlite_helper::Column for #field_enum {
+            fn column(&self) -> &'static str {
+                match self {
+                    #(#field_enum::#variants => #columns),*
+                }
+            }
         }
     }
 }
 
-fn body_update(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
-    let struct_name_string = String::from(struct_name.to_string());
-    let idents: Vec<_> = fields_named.named.iter().map(|f| &f.ident).collect();
-    let first_ident = idents.get(0).unwrap();
-    
-    let var_strings: Vec<_> = idents.iter().filter_map(|&opt| opt.as_ref()).map(|ident| ident.to_string()).collect();
-    let mut up_strings: Vec<String> = Vec::new();
+fn field_variant(field: &FieldAttribute) -> Ident {
+    format_ident!("{}", RenameRule::Pascal.apply(&field.ident_name))
+}
 
-    for index in 1..idents.len() {
-        up_strings.push(format!("{} = ?{}", var_strings.get(index).unwrap(), index));
+/// Renders a `{Struct}Filter` as the condition of a `WHERE` clause, pushing its values onto `to_sql_objects`.
+/// `request_conditions` pushes the conditions of a single request, it differs between the two drivers.
+fn filter_where_clause(struct_name: &Ident, to_sql_type: proc_macro2::TokenStream, request_conditions: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    quote! {
+        impl #filter_enum {
+            fn where_clause<'a>(&'a self, to_sql_objects: &mut Vec<#to_sql_type>) -> String {
+                let (filters, operator, empty) = match self {
+                    #filter_enum::Where(request) => {
+                        let mut conditions: Vec<String> = Vec::new();
+                        #request_conditions
+                        if conditions.is_empty() {
+                            return String::from("TRUE");
+                        }
+                        return conditions.join(" AND ");
+                    },
+                    #filter_enum::Not(filter) => return format!("NOT ({})", filter.where_clause(to_sql_objects)),
+                    #filter_enum::And(filters) => (filters, " AND ", "TRUE"),
+                    #filter_enum::Or(filters) => (filters, " OR ", "FALSE"),
+                };
+
+                if filters.is_empty() {
+                    return String::from(empty);
+                }
+                let conditions: Vec<String> = filters.iter().map(|filter| filter.where_clause(to_sql_objects)).collect();
+                format!("({})", conditions.join(operator))
+            }
+        }
     }
+}
 
-    let joined_up_strings: String = up_strings.join(",\n");
-    let query_string: String = format!("UPDATE {} SET {} WHERE {} = {{}} ;", struct_name_string, joined_up_strings, var_strings.get(0).unwrap());
-    let skipped_idents: Vec<_> = idents.iter().skip(1).map(|f| *f).collect();
-    
-     
+/// `{Struct}Patch` holds the columns to change in a partial update, only the `Some` fields are set.
+fn patch_struct(fields: &[FieldAttribute], struct_name: &Ident) -> proc_macro2::TokenStream {
+    let patch_struct: &Ident = &format_ident!("{}Patch", struct_name);
+    let idents: Vec<_> = fields.iter().map(|f| f.ident).collect();
+    let types: Vec<_> = fields.iter().map(|f| &f.field.ty).collect();
     quote! {
-        pub fn update(&self, conn: &rusqlite::Connection) -> anyhow::Result<usize> {
-            let query_string: String = format!(#query_string, self.#first_ident);
-            let stmt: usize = conn.execute(&query_string, rusqlite::params![#( self.#skipped_idents),*])?;  
-            return Ok(stmt);
+        #[derive(Default, Clone)]
+        pub struct #patch_struct {
+            #(pub #idents : Option<#types>),*
         }
+
     }
 }
 
-fn body_delete(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
+/// The type of a `key` argument, a tuple for composite keys, and the statement binding a reference to each of its values.
+fn key_tokens(primary_fields: &[&FieldAttribute]) -> (proc_macro2::TokenStream, Vec<Ident>, proc_macro2::TokenStream) {
+    let types: Vec<&Type> = primary_fields.iter().map(|f| &f.field.ty).collect();
+    let key_idents: Vec<Ident> = (0..primary_fields.len()).map(|i| format_ident!("key_{}", i)).collect();
 
-    let struct_name_string = String::from(struct_name.to_string());
-    let idents: Vec<_> = fields_named.named.iter().map(|f| &f.ident).collect();
+    if primary_fields.len() == 1 {
+        let key_type = types[0];
+        let key_ident = &key_idents[0];
+        return (quote!(#key_type), key_idents.clone(), quote!(let #key_ident = &key;));
+    }
 
-    let first_ident = idents.get(0).unwrap().as_ref().unwrap();
-    let query_string = format!("DELETE FROM {} WHERE {} = {{}}", struct_name_string, first_ident.to_string());
-    quote! {
-        pub fn delete(&self, conn:&rusqlite::Connection) -> anyhow::Result<usize> {
-            let query_string: String = format!(#query_string, self.#first_ident);
-            let stmt: usize = conn.execute(&query_string, rusqlite::params![])?;
-            return Ok(stmt);
+    (quote!((#(#types),*)), key_idents.clone(), quote!(let (#(#key_idents),*) = &key;))
+}
+
+/// Returns `T` when `ty` is written as `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(type_path) = ty {
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != "Option" {
+            return None;
+        }
+        if let PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                return Some(inner);
+            }
         }
     }
+    None
 }
 
-#[proc_macro_derive(LibSqlQueryable, attributes(primary))]
-pub fn libsql_macro(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = parse_macro_input!(input as DeriveInput);
-    let new_functions: proc_macro2::TokenStream;
-    // Check if the input is a struct
-    if let Data::Struct(data_struct) = ast.data {
-        let struct_name = ast.ident;
-
-        match data_struct.fields {
-            Fields::Named(fields_named) => {
-                let request = request_struct(&fields_named, &struct_name);
-                let get_fn_tokens = libsql_body_get(&fields_named, &struct_name);
-                let add_fn_tokens = libsql_body_add(&fields_named, &struct_name);
-                let update_fn_tokens = libsql_body_update(&fields_named, &struct_name);
-                println!("{}", update_fn_tokens);
-                new_functions = quote! {
-                    #request
-
-                    impl #struct_name {
-                        #get_fn_tokens
-                        #add_fn_tokens
-                        #update_fn_tokens
+/// Maps a rust type onto the SQLite type affinity used for its column.
+/// Types we don't recognise get no declared type, which SQLite stores as-is.
+fn sql_type_affinity(ty: &Type) -> Option<&'static str> {
+    match ty {
+        Type::Reference(reference) => sql_type_affinity(&reference.elem),
+        Type::Array(array) => match sql_type_affinity(&array.elem) {
+            Some("INTEGER") => Some("BLOB"),
+            _ => None,
+        },
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last()?;
+            match segment.ident.to_string().as_str() {
+                "i8" | "i16" | "i32" | "i64" | "isize" |
+                "u8" | "u16" | "u32" | "u64" | "usize" | "bool" => Some("INTEGER"),
+                "f32" | "f64" => Some("REAL"),
+                "String" | "str" | "char" => Some("TEXT"),
+                "Vec" => {
+                    if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                        if let Some(GenericArgument::Type(Type::Path(inner))) = args.args.first() {
+                            if inner.path.is_ident("u8") {
+                                return Some("BLOB");
+                            }
+                        }
                     }
-                }
+                    None
+                },
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Builds the `CREATE TABLE` statement for a struct, the second string being the `IF NOT EXISTS` form.
+fn create_table_strings(fields: &[FieldAttribute], table: &TableAttribute) -> syn::Result<(String, String)> {
+    let table_name = quote_identifier(&table.table_name);
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+    let single_primary = primary_fields.len() == 1;
+
+    let mut definitions: Vec<String> = fields.iter().map(|f| {
+        let (ty, nullable) = match option_inner_type(&f.field.ty) {
+            Some(inner) => (inner, true),
+            None => (&f.field.ty, false),
+        };
+
+        let mut definition = quote_identifier(&f.column_name);
+        if f.is_autoincrement {
+            // AUTOINCREMENT is only allowed on an INTEGER PRIMARY KEY column
+            definition.push_str(" INTEGER PRIMARY KEY AUTOINCREMENT");
+        } else {
+            if let Some(affinity) = sql_type_affinity(ty) {
+                definition.push(' ');
+                definition.push_str(affinity);
+            }
+            if single_primary && is_key(f, &primary_fields) {
+                definition.push_str(" PRIMARY KEY");
             }
-            _ => panic!("Only structs with named fields are supported"),
         }
-    } else {
-        panic!("Only structs are supported");
+        if !nullable {
+            definition.push_str(" NOT NULL");
+        }
+        if f.is_unique {
+            definition.push_str(" UNIQUE");
+        }
+        definition
+    }).collect();
+
+    if primary_fields.len() > 1 {
+        let primary_names: Vec<String> = primary_fields.iter().map(|f| quote_identifier(&f.column_name)).collect();
+        definitions.push(format!("PRIMARY KEY({})", primary_names.join(", ")));
+    }
+    let unique_key: Vec<&FieldAttribute> = unique_key_fields(fields, table);
+    if !unique_key.is_empty() {
+        let unique_names: Vec<String> = unique_key.iter().map(|f| quote_identifier(&f.column_name)).collect();
+        definitions.push(format!("UNIQUE({})", unique_names.join(", ")));
     }
 
-    return TokenStream::from(new_functions);
+    let joined_definitions = definitions.join(", ");
+    let create_string = format!("CREATE TABLE {} ({});", table_name, joined_definitions);
+    let create_if_not_exists_string = format!("CREATE TABLE IF NOT EXISTS {} ({});", table_name, joined_definitions);
+    return Ok((create_string, create_if_not_exists_string));
 }
 
-fn libsql_body_get(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
-    let request_struct: &Ident = &Ident::new(&format!("{}Request", struct_name), proc_macro2::Span::call_site());
-    let struct_name_string = String::from(struct_name.to_string());
-    let idents: Vec<_> = fields_named.named.iter().map(|f| &f.ident).collect();
-    let types: Vec<_> = fields_named.named.iter().map(|f| &f.ty).collect();
-    let conditions: Vec<String> = idents.iter().map(|ident| format!("AND {} = ", ident.as_ref().unwrap())).collect();
+fn body_create_table(fields: &[FieldAttribute], table: &TableAttribute) -> proc_macro2::TokenStream {
+    let (create_string, create_if_not_exists_string) = match create_table_strings(fields, table) {
+        Ok(strings) => strings,
+        Err(err) => return err.to_compile_error(),
+    };
 
     quote! {
-        pub async fn get(client: &libsql_client::Client, request: #request_struct) -> anyhow::Result<Self> {
-            let mut query_string: String = format!("SELECT * FROM {} WHERE TRUE = TRUE", #struct_name_string);
-            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
-            #(
-                let mut #idents: #types;
-                if let Some(i) = request.#idents {
-                    query_string = format!("{}\n{}?", query_string, #conditions);
-                    #idents = i.clone();
-                    to_sql_objects.push(#idents.into());
-                }
-            )*
+        pub fn create_table_sql() -> &'static str {
+            #create_string
+        }
 
-            let obj: #struct_name = client.execute(libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?
-                .rows
-                .iter()
-                .next()
-                .map(libsql_client::de::from_row::<#struct_name>)
-                .transpose()?
-                .context("No rows returned")?;
-            return Ok(obj);
+        pub fn create_table_if_not_exists_sql() -> &'static str {
+            #create_if_not_exists_string
         }
 
-        pub async fn get_many(client: &libsql_client::Client, request: #request_struct) -> anyhow::Result<Vec<Self>> {
-            let mut query_string: String = format!("SELECT * FROM {} WHERE TRUE = TRUE", #struct_name_string);
-            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
-            #(
-                let mut #idents: #types;
-                if let Some(i) = request.#idents {
-                    query_string = format!("{}\n{}?", query_string, #conditions);
-                    #idents = i.clone();
-                    to_sql_objects.push(#idents.into());
-                }
-            )*
+        pub fn create_table(conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<()> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            conn.execute(Self::create_table_sql(), [])?;
+            return Ok(());
+        }
 
-            let obj_vector = client.execute(libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?
-                .rows
-                .iter()
-                .map(libsql_client::de::from_row)
-                .collect::<Result<Vec<#struct_name>, _>>()?;
+        pub fn create_table_if_not_exists(conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<()> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            conn.execute(Self::create_table_if_not_exists_sql(), [])?;
+            return Ok(());
+        }
+    }
+}
+
+fn body_get(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let field_enum: &Ident = &format_ident!("{}Field", struct_name);
+    let table_name = quote_identifier(&table.table_name);
+    let idents: Vec<_> = fields.iter().map(|f| f.ident).collect();
+    let names: Vec<&String> = fields.iter().map(|f| &f.ident_name).collect();
+    let select_string: String = select_string(fields);
+    let key_variants: Vec<Ident> = primary_fields(fields).iter().map(|f| field_variant(f)).collect();
+
+    quote! {
+        /// Returns the only row matching `filter`, failing with `Error::NotFound` or `Error::NotUnique` otherwise.
+        pub fn get(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Self> {
+            return Self::get_optional(conn, filter)?.ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+
+        /// Same as `get`, with `None` when no row matches.
+        pub fn get_optional(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<Self>> {
+            let mut obj_vector = Self::get_many_with(conn, filter, ::sqlite_helper::QueryOptions::new().limit(2))?;
+            if obj_vector.len() > 1 {
+                return Err(::sqlite_helper::Error::NotUnique);
+            }
+            return Ok(obj_vector.pop());
+        }
+
+        /// Returns the first row matching `filter` in primary key order, failing with `Error::NotFound` when none does.
+        pub fn get_first(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Self> {
+            let options = ::sqlite_helper::QueryOptions::new()#(.asc(#field_enum::#key_variants))*.limit(1);
+            let mut obj_vector = Self::get_many_with(conn, filter, options)?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+
+        pub fn get_many(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Vec<Self>> {
+            return Self::get_many_with(conn, filter, ::sqlite_helper::QueryOptions::new());
+        }
+
+        /// Same as `get_many`, with the `ORDER BY`, `LIMIT` and `OFFSET` set in `options`.
+        pub fn get_many_with(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>, options: ::sqlite_helper::QueryOptions<#field_enum>) -> ::sqlite_helper::Result<Vec<Self>> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let query_string: String = format!("SELECT {} FROM {} WHERE {}{}", #select_string, #table_name, filter.where_clause(&mut to_sql_objects), options.sql());
+            let mut stmt = conn.prepare(&query_string)?;
+            let obj_iter = stmt.query_map(rusqlite::params_from_iter(to_sql_objects), Self::from_row)?;
+
+            let obj_vector = obj_iter.collect::<Result<Vec<Self>, _>>()?;
 
             return Ok(obj_vector);
         }
+
+        /// Reads a row selected with the struct's columns, see `select_string`.
+        fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+            return Ok(#struct_name {
+                #(#idents : row.get(#names)?,)*
+            });
+        }
+
+        /// Runs a statement with a `RETURNING` clause and reads back the rows it returns.
+        fn returning_rows(conn: &rusqlite::Connection, query_string: &str, params: impl rusqlite::Params) -> ::sqlite_helper::Result<Vec<Self>> {
+            let mut stmt = conn.prepare_cached(query_string)?;
+            let obj_iter = stmt.query_map(params, Self::from_row)?;
+
+            let obj_vector = obj_iter.collect::<Result<Vec<Self>, _>>()?;
+
+            return Ok(obj_vector);
+        }
+
+        /// Calls `f` with every row matching `filter` as it is read, instead of collecting them into a Vec.
+        /// An error returned by `f` stops the iteration and is returned, `f` can use any error type a `sqlite_helper::Error` converts into.
+        pub fn for_each<E: From<::sqlite_helper::Error>>(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>, f: impl FnMut(::sqlite_helper::Result<Self>) -> Result<(), E>) -> Result<(), E> {
+            return Self::for_each_with(conn, filter, ::sqlite_helper::QueryOptions::new(), f);
+        }
+
+        /// Same as `for_each`, with the `ORDER BY`, `LIMIT` and `OFFSET` set in `options`.
+        pub fn for_each_with<E: From<::sqlite_helper::Error>>(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>, options: ::sqlite_helper::QueryOptions<#field_enum>, mut f: impl FnMut(::sqlite_helper::Result<Self>) -> Result<(), E>) -> Result<(), E> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let query_string: String = format!("SELECT {} FROM {} WHERE {}{}", #select_string, #table_name, filter.where_clause(&mut to_sql_objects), options.sql());
+            let mut stmt = conn.prepare(&query_string).map_err(::sqlite_helper::Error::from)?;
+            let obj_iter = stmt.query_map(rusqlite::params_from_iter(to_sql_objects), Self::from_row).map_err(::sqlite_helper::Error::from)?;
+
+            for obj in obj_iter {
+                f(obj.map_err(::sqlite_helper::Error::from))?;
+            }
+            return Ok(());
+        }
     }
 }
 
-#[derive(Debug)]
-struct FieldAttribute<'a> {
-    pub is_primary: bool,
-    pub is_autoincrement: bool,
-    pub field: &'a Field,
-    pub ident: &'a Ident,
-    pub ident_name: String
-}
+/// Keyset pagination, rows are ordered by the chosen columns followed by the primary key and each page starts after the
+/// values of the last row of the previous one, which `page_after` hands back as a `Cursor`.
+fn body_page(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let field_enum: &Ident = &format_ident!("{}Field", struct_name);
+    let table_name = quote_identifier(&table.table_name);
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+    let key_variants: Vec<Ident> = primary_fields.iter().map(|f| field_variant(f)).collect();
+    let variants: Vec<Ident> = fields.iter().map(field_variant).collect();
+    let idents: Vec<&Ident> = fields.iter().map(|f| f.ident).collect();
+    let select_string: String = select_string(fields);
 
-fn parse_field(field: &Field) -> FieldAttribute{
-    let mut is_primary = false;
-    let mut is_autoincrement = false;
-    for attr in &field.attrs {
-        if let Some(ident) = attr.path().get_ident() {
-            if ident == "primary" {
-                is_primary = true;
+    quote! {
+        /// Returns up to `limit` rows matching `filter` in primary key order, starting after `cursor`.
+        pub fn page_after(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>, cursor: Option<&::sqlite_helper::Cursor>, limit: u64) -> ::sqlite_helper::Result<::sqlite_helper::Page<Self>> {
+            return Self::page_after_by(conn, filter, &[], cursor, limit);
+        }
+
+        /// Same as `page_after`, ordered by `columns` and then by the primary key. NULLs sort before every other value.
+        pub fn page_after_by(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>, columns: &[#field_enum], cursor: Option<&::sqlite_helper::Cursor>, limit: u64) -> ::sqlite_helper::Result<::sqlite_helper::Page<Self>> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            if limit == 0 {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("page_after needs a limit above 0")));
+            }
+            let mut columns: Vec<#field_enum> = columns.to_vec();
+            for key in &[#(#field_enum::#key_variants),*] {
+                if !columns.contains(key) {
+                    columns.push(*key);
+                }
+            }
+
+            let filter: #filter_enum = filter.into();
+            let cursor_values: Vec<::sqlite_helper::CursorValue> = match cursor {
+                Some(cursor) => cursor.decode()?,
+                None => Vec::new(),
+            };
+            if cursor.is_some() && cursor_values.len() != columns.len() {
+                return Err(::sqlite_helper::Error::InvalidCursor);
+            }
+            let cursor_params: Vec<rusqlite::types::Value> = cursor_values.iter().cloned().map(|value| match value {
+                ::sqlite_helper::CursorValue::Null => rusqlite::types::Value::Null,
+                ::sqlite_helper::CursorValue::Integer(integer) => rusqlite::types::Value::Integer(integer),
+                ::sqlite_helper::CursorValue::Real(real) => rusqlite::types::Value::Real(real),
+                ::sqlite_helper::CursorValue::Text(text) => rusqlite::types::Value::Text(text),
+                ::sqlite_helper::CursorValue::Blob(blob) => rusqlite::types::Value::Blob(blob),
+            }).collect();
+
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let mut query_string: String = format!("SELECT {} FROM {} WHERE {}", #select_string, #table_name, filter.where_clause(&mut to_sql_objects));
+            let mut options = ::sqlite_helper::QueryOptions::new().limit(limit.saturating_add(1));
+            for column in &columns {
+                options = options.asc(*column);
+            }
+            if !cursor_values.is_empty() {
+                let column_names: Vec<&str> = columns.iter().map(::sqlite_helper::Column::column).collect();
+                let (condition, params) = ::sqlite_helper::after_condition(&column_names, &cursor_values);
+                query_string = format!("{} AND {}", query_string, condition);
+                for index in params {
+                    to_sql_objects.push(&cursor_params[index]);
+                }
+            }
+            query_string = format!("{}{}", query_string, options.sql());
 
-                 let _ = attr.parse_nested_meta(|meta| {
-                     if let Some(meta_ident) = meta.path.get_ident() {
-                         if meta_ident == "autoincrement" {
-                            is_autoincrement = true;
-                         }
-                     }
-                     Ok(())
-                 });
-                
+            let mut stmt = conn.prepare(&query_string)?;
+            let mut rows: Vec<Self> = stmt.query_map(rusqlite::params_from_iter(to_sql_objects), Self::from_row)?.collect::<Result<Vec<Self>, _>>()?;
+
+            let mut next_cursor: Option<::sqlite_helper::Cursor> = None;
+            if rows.len() as u64 > limit {
+                rows.truncate(limit as usize);
+                if let Some(last) = rows.last() {
+                    let values = columns.iter().map(|column| last.cursor_value(*column)).collect::<::sqlite_helper::Result<Vec<::sqlite_helper::CursorValue>>>()?;
+                    next_cursor = Some(::sqlite_helper::Cursor::encode(&values));
+                }
             }
+            return Ok(::sqlite_helper::Page { rows, next_cursor });
+        }
+
+        fn cursor_value(&self, column: #field_enum) -> ::sqlite_helper::Result<::sqlite_helper::CursorValue> {
+            let output: rusqlite::types::ToSqlOutput = match column {
+                #(#field_enum::#variants => rusqlite::ToSql::to_sql(&self.#idents)?),*
+            };
+            let value: rusqlite::types::ValueRef = match &output {
+                rusqlite::types::ToSqlOutput::Borrowed(value) => *value,
+                rusqlite::types::ToSqlOutput::Owned(value) => value.into(),
+                _ => return Err(::sqlite_helper::Error::conversion(format!("{:?} can't be stored in a cursor", column))),
+            };
+            return Ok(match value {
+                rusqlite::types::ValueRef::Null => ::sqlite_helper::CursorValue::Null,
+                rusqlite::types::ValueRef::Integer(integer) => ::sqlite_helper::CursorValue::Integer(integer),
+                rusqlite::types::ValueRef::Real(real) => ::sqlite_helper::CursorValue::Real(real),
+                rusqlite::types::ValueRef::Text(text) => ::sqlite_helper::CursorValue::Text(String::from_utf8(text.to_vec())?),
+                rusqlite::types::ValueRef::Blob(blob) => ::sqlite_helper::CursorValue::Blob(blob.to_vec()),
+            });
         }
     }
-    
-    let ident = &field.ident.as_ref().unwrap();
-    let ident_name = ident.to_string();
+}
 
-    return FieldAttribute {
-        is_primary,
-        is_autoincrement,
-        field,
-        ident,
-        ident_name
-    };
+/// The type of a field `sum`, `min`, `max` and `avg` apply to, `Option<T>` fields use `T`, and whether it is an integer.
+/// Only integer and floating point fields are numeric, `bool` is stored as an INTEGER but isn't one.
+fn numeric_type(ty: &Type) -> Option<(&Type, bool)> {
+    let inner = option_inner_type(ty).unwrap_or(ty);
+    if let Type::Path(type_path) = inner {
+        if type_path.path.is_ident("bool") {
+            return None;
+        }
+    }
+    match sql_type_affinity(inner) {
+        Some("INTEGER") => Some((inner, true)),
+        Some("REAL") => Some((inner, false)),
+        _ => None,
+    }
 }
-        
 
-fn libsql_body_add(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
-    let struct_name_string = String::from(struct_name.to_string());
-    let fields: Vec<FieldAttribute> = fields_named.named.iter().map(|f| parse_field(f)).collect();
+/// `count`, `exists` and a typed `sum_`, `min_`, `max_` and `avg_` method for every numeric field.
+fn body_aggregate(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let table_name = quote_identifier(&table.table_name);
+    let aggregates: Vec<proc_macro2::TokenStream> = fields.iter().filter_map(|f| {
+        let (ty, is_integer) = numeric_type(&f.field.ty)?;
+        let column = quote_identifier(&f.column_name);
+        let sum_type = if is_integer { quote!(i64) } else { quote!(f64) };
+        let sum = format_ident!("sum_{}", f.ident_name);
+        let min = format_ident!("min_{}", f.ident_name);
+        let max = format_ident!("max_{}", f.ident_name);
+        let avg = format_ident!("avg_{}", f.ident_name);
+        Some(quote! {
+            /// `None` when no row matches `filter` or every value is NULL.
+            pub fn #sum(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<#sum_type>> {
+                return Self::aggregate(conn, "SUM", #column, filter.into());
+            }
 
-    let filtered_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !f.is_autoincrement).collect();
-    let filtered_idents: Vec<&Ident> = filtered_fields.iter().map(|f| f.ident).collect();
-    let vals: Vec<String> = (0..filtered_fields.len()).map(|_| "?".to_string()).collect();
-    let joined_vals = vals.join(", ");
+            pub fn #min(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<#ty>> {
+                return Self::aggregate(conn, "MIN", #column, filter.into());
+            }
+
+            pub fn #max(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<#ty>> {
+                return Self::aggregate(conn, "MAX", #column, filter.into());
+            }
+
+            pub fn #avg(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<f64>> {
+                return Self::aggregate(conn, "AVG", #column, filter.into());
+            }
+        })
+    }).collect();
 
-    let var_strings: Vec<_> = filtered_idents.iter().map(|ident| ident.to_string()).collect();
-    let joined_vars: String = var_strings.join(", ");
-    let query_string: String = format!("INSERT INTO {} ({}) VALUES ({});", struct_name_string, joined_vars, joined_vals);
-    
     quote! {
-        pub async fn add(&self, client: &libsql_client::Client) -> anyhow::Result<usize> {
-            let query_string: &str = #query_string;
-            let mut params: Vec<libsql_client::Value> = Vec::new();
-            #(params.push(self.#filtered_idents.clone().into());)*
-            let stmt = client.execute(libsql_client::Statement::with_args(query_string,  &params)).await?;
-            return Ok(stmt.rows_affected as usize);
+        /// Counts the rows matching `filter` without loading them.
+        pub fn count(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<u64> {
+            let count: Option<i64> = Self::aggregate(conn, "COUNT", "*", filter.into())?;
+            return Ok(count.unwrap_or(0) as u64);
+        }
+
+        /// Whether any row matches `filter`.
+        pub fn exists(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<bool> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let query_string: String = format!("SELECT EXISTS(SELECT 1 FROM {} WHERE {})", #table_name, filter.where_clause(&mut to_sql_objects));
+            let exists: bool = conn.query_row(&query_string, rusqlite::params_from_iter(to_sql_objects), |row| row.get(0))?;
+            return Ok(exists);
         }
+
+        fn aggregate<T: rusqlite::types::FromSql>(conn: &impl ::sqlite_helper::Executor, function: &str, column: &str, filter: #filter_enum) -> ::sqlite_helper::Result<Option<T>> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let query_string: String = format!("SELECT {}({}) FROM {} WHERE {}", function, column, #table_name, filter.where_clause(&mut to_sql_objects));
+            let value: Option<T> = conn.query_row(&query_string, rusqlite::params_from_iter(to_sql_objects), |row| row.get(0))?;
+            return Ok(value);
+        }
+
+        #(#aggregates)*
     }
 }
 
-fn libsql_body_update(fields_named: &FieldsNamed, struct_name: &Ident) -> proc_macro2::TokenStream {
-    let struct_name_string = String::from(struct_name.to_string());
-    let idents: Vec<_> = fields_named.named.iter().map(|f| &f.ident).collect();
-    let first_ident = idents.get(0).unwrap();
+/// Pushes a condition onto `conditions` for every `Some` filter in `request`, pushing references to its values onto `to_sql_objects`.
+fn request_conditions(fields: &[FieldAttribute]) -> proc_macro2::TokenStream {
+    let idents: Vec<&Ident> = fields.iter().map(|f| f.ident).collect();
+    let columns: Vec<String> = fields.iter().map(|f| quote_identifier(&f.column_name)).collect();
 
-    let fields: Vec<FieldAttribute> = fields_named.named.iter().map(|f| parse_field(f)).collect();
-    let primary_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| f.is_primary).collect();
-    let non_primary_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !f.is_primary).collect();
+    quote! {
+        #(
+            if let Some(filter) = &request.#idents {
+                let mut params = Vec::new();
+                conditions.push(filter.condition(#columns, &mut params));
+                for param in params {
+                    match param {
+                        ::sqlite_helper::Param::Value(value) => to_sql_objects.push(value),
+                        ::sqlite_helper::Param::Text(text) => to_sql_objects.push(text),
+                    }
+                }
+            }
+        )*
+    }
+}
 
-    let primary_idents: Vec<&Ident> = primary_fields.iter().map(|f| f.ident).collect();
-    let non_primary_idents: Vec<&Ident> = non_primary_fields.iter().map(|f| f.ident).collect();
+/// Pushes a `column = ?` assignment onto `set_strings` for every `Some` field in `patch`, pushing a reference to its value onto `to_sql_objects`.
+fn patch_assignments(fields: &[FieldAttribute], patch: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let idents: Vec<&Ident> = fields.iter().map(|f| f.ident).collect();
+    let assignments: Vec<String> = fields.iter().map(|f| format!("{} = ?", quote_identifier(&f.column_name))).collect();
 
-    let up_strings: Vec<String> = non_primary_idents.iter().map(|ident| format!("{} = ?", ident)).collect();
-    let where_strings: Vec<String> = primary_idents.iter().map(|ident| format!("{} = ?", ident)).collect();
+    quote! {
+        #(
+            if let Some(value) = &#patch.#idents {
+                to_sql_objects.push(value);
+                set_strings.push(#assignments.to_string());
+            }
+        )*
+    }
+}
 
+fn body_add(fields: &[FieldAttribute], table: &TableAttribute) -> proc_macro2::TokenStream {
 
-    let joined_up_strings: String = up_strings.join(",\n");
-    let joined_where_strings: String = where_strings.join(" AND ");
-    let query_string: String = format!("UPDATE {} SET {} WHERE {};", struct_name_string, joined_up_strings, joined_where_strings);
+    let filtered_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !f.is_autoincrement).collect();
+    let filtered_idents: Vec<&Ident> = filtered_fields.iter().map(|f| f.ident).collect();
+    let query_string: String = insert_string(&filtered_fields, table, |i| format!("?{}", i + 1));
+    let returning_string: String = returning_string(&query_string, fields);
+    let (add_chunks, returning_chunks) = if filtered_fields.is_empty() {
+        // Only the autoincrement field, which a multi-row INSERT has no way of leaving to its default.
+        (quote! {
+            let mut added: usize = 0;
+            for row in rows {
+                added += row.add(conn)?;
+            }
+            return Ok(added);
+        }, quote! {
+            let mut added: Vec<Self> = Vec::with_capacity(rows.len());
+            for row in rows {
+                added.push(row.add_returning(conn)?);
+            }
+            return Ok(added);
+        })
+    } else {
+        let insert_prefix = insert_prefix(&filtered_fields, table);
+        let select_string = select_string(fields);
+        let column_count = filtered_fields.len();
+        (quote! {
+            let mut added: usize = 0;
+            for chunk in rows.chunks(::sqlite_helper::rows_per_statement(#column_count)) {
+                let query_string: String = format!("{} VALUES {}", #insert_prefix, ::sqlite_helper::values_placeholders(chunk.len(), #column_count));
+                let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::with_capacity(chunk.len() * #column_count);
+                for row in chunk {
+                    #(to_sql_objects.push(&row.#filtered_idents);)*
+                }
+                added += conn.execute(&query_string, rusqlite::params_from_iter(to_sql_objects))?;
+            }
+            return Ok(added);
+        }, quote! {
+            let mut added: Vec<Self> = Vec::with_capacity(rows.len());
+            for chunk in rows.chunks(::sqlite_helper::rows_per_statement(#column_count)) {
+                let query_string: String = format!("{} VALUES {} RETURNING {}", #insert_prefix, ::sqlite_helper::values_placeholders(chunk.len(), #column_count), #select_string);
+                let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::with_capacity(chunk.len() * #column_count);
+                for row in chunk {
+                    #(to_sql_objects.push(&row.#filtered_idents);)*
+                }
+                added.extend(Self::returning_rows(conn, &query_string, rusqlite::params_from_iter(to_sql_objects))?);
+            }
+            return Ok(added);
+        })
+    };
+    let insert_mut_fn_tokens = set_autoincrement_tokens(fields).map(|set_autoincrement| quote! {
+        /// Adds the struct and writes the id SQLite picked back into its autoincrement field.
+        pub fn insert_mut(&mut self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<i64> {
+            let rowid: i64 = self.insert(conn)?;
+            #set_autoincrement
+            return Ok(rowid);
+        }
+    });
      
-    println!("{:?}", non_primary_fields);
     quote! {
-        pub async fn update(&self, client: &libsql_client::Client) -> anyhow::Result<usize> {
-            let query_string: String = #query_string.to_string();
-            let mut params: Vec<libsql_client::Value> = Vec::new();
-            #(params.push(self.#non_primary_idents.clone().into());)*
-            #(params.push(self.#primary_idents.clone().into());)*
-            let stmt = client.execute(libsql_client::Statement::with_args(&query_string, &params)).await?;  
-            return Ok(stmt.rows_affected as usize);
+        pub fn add(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let query_string: &str = #query_string;
+            let stmt: usize = conn.prepare_cached(query_string)?.execute(rusqlite::params! [#( self.#filtered_idents),*])?;  
+            return Ok(stmt);
+        }
+
+        /// Adds the struct and returns the rowid of the new row.
+        pub fn insert(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<i64> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            self.add(conn)?;
+            return Ok(conn.last_insert_rowid());
+        }
+
+        /// Adds every struct in `rows` with multi-row INSERTs, as many rows per statement as SQLite's parameter limit allows,
+        /// and returns the number of rows added. The INSERTs run in a savepoint, so either every row is added or none is.
+        pub fn add_many(conn: &impl ::sqlite_helper::Executor, rows: &[Self]) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            if rows.is_empty() {
+                return Ok(0);
+            }
+
+            return Self::in_add_many_savepoint(conn, || -> ::sqlite_helper::Result<usize> {
+                #add_chunks
+            });
+        }
+
+        /// Adds the struct and returns the row as stored, with the id SQLite picked and any column defaults.
+        pub fn add_returning(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<Self> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let mut obj_vector = Self::returning_rows(conn, #returning_string, rusqlite::params![#( self.#filtered_idents),*])?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
         }
+
+        /// Same as `add_many`, returning the rows as stored. SQLite doesn't promise they come back in the order of `rows`.
+        pub fn add_many_returning(conn: &impl ::sqlite_helper::Executor, rows: &[Self]) -> ::sqlite_helper::Result<Vec<Self>> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            if rows.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            return Self::in_add_many_savepoint(conn, || -> ::sqlite_helper::Result<Vec<Self>> {
+                #returning_chunks
+            });
+        }
+
+        /// Runs `f` in the savepoint shared by `add_many` and `add_many_returning`, undoing its INSERTs when it fails.
+        fn in_add_many_savepoint<T>(conn: &rusqlite::Connection, f: impl FnOnce() -> ::sqlite_helper::Result<T>) -> ::sqlite_helper::Result<T> {
+            conn.execute_batch("SAVEPOINT add_many")?;
+            let added = f();
+            match added {
+                Ok(_) => conn.execute_batch("RELEASE add_many")?,
+                // The error from the INSERT is the one worth returning.
+                Err(_) => {
+                    let _ = conn.execute_batch("ROLLBACK TO add_many; RELEASE add_many");
+                },
+            }
+            return added;
+        }
+
+        #insert_mut_fn_tokens
+    }
+}
+
+/// The INSERT statements for `upsert`, `insert_or_ignore` and `insert_or_replace`, with `placeholder` giving the parameter for
+/// the nth value, and the fields each of them binds.
+fn upsert_strings<'a, 'b>(fields: &'b [FieldAttribute<'a>], table: &TableAttribute, placeholder: impl Fn(usize) -> String + Copy) -> (String, Vec<&'b FieldAttribute<'a>>, String, Vec<&'b FieldAttribute<'a>>) {
+    // The conflict target is the `#[table(unique(...))]` key, or the `#[unique]` field when there is exactly one, or the
+    // primary key. Several `#[unique]` fields are separate constraints, none of which is the obvious target.
+    let unique_key: Vec<&FieldAttribute> = unique_key_fields(fields, table);
+    let unique_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| f.is_unique).collect();
+    let target_fields: Vec<&FieldAttribute> = if !unique_key.is_empty() {
+        unique_key
+    } else if unique_fields.len() == 1 {
+        unique_fields
+    } else {
+        primary_fields(fields)
+    };
+    // An autoincrement key is only inserted when it is an `Option` and the upsert matches on it, otherwise SQLite picks it
+    // like it does for `add`. A plain integer id is a placeholder until the row is added, so it never identifies a row.
+    let upsert_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !f.is_autoincrement || (is_key(f, &target_fields) && is_optional_key(f))).collect();
+    // The primary key of the existing row is kept when the upsert matches on a unique key instead.
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+    let set_strings: Vec<String> = upsert_fields.iter()
+        .filter(|f| !is_key(f, &target_fields) && !is_key(f, &primary_fields))
+        .map(|f| format!("{0} = excluded.{0}", quote_identifier(&f.column_name)))
+        .collect();
+    let target_names: Vec<String> = target_fields.iter().map(|f| quote_identifier(&f.column_name)).collect();
+    let insert = insert_string(&upsert_fields, table, placeholder);
+    let conflict = if upsert_fields.is_empty() {
+        // `DEFAULT VALUES` can't take an upsert clause, and the id SQLite picks never conflicts anyway.
+        String::new()
+    } else if set_strings.is_empty() {
+        format!("ON CONFLICT ({}) DO NOTHING", target_names.join(", "))
+    } else {
+        format!("ON CONFLICT ({}) DO UPDATE SET {}", target_names.join(", "), set_strings.join(", "))
+    };
+    let upsert_string = if conflict.is_empty() { insert } else { format!("{} {};", insert.trim_end_matches(';'), conflict) };
+
+    // `INSERT OR IGNORE` and `INSERT OR REPLACE` also conflict on the primary key, so an `Option` autoincrement key is
+    // inserted as well, `None` leaving it to SQLite.
+    let or_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !f.is_autoincrement || is_optional_key(f)).collect();
+    let or_string = insert_string(&or_fields, table, placeholder);
+    return (upsert_string, upsert_fields, or_string, or_fields);
+}
+
+/// Whether the autoincrement `field` can be left to SQLite while still naming an existing row, which takes an `Option`.
+fn is_optional_key(field: &FieldAttribute) -> bool {
+    option_inner_type(&field.field.ty).is_some()
+}
+
+fn body_upsert(fields: &[FieldAttribute], table: &TableAttribute) -> proc_macro2::TokenStream {
+    let (upsert_string, upsert_fields, or_string, or_fields) = upsert_strings(fields, table, |i| format!("?{}", i + 1));
+    let upsert_idents: Vec<&Ident> = upsert_fields.iter().map(|f| f.ident).collect();
+    let or_idents: Vec<&Ident> = or_fields.iter().map(|f| f.ident).collect();
+    let ignore_string = or_string.replacen("INSERT INTO", "INSERT OR IGNORE INTO", 1);
+    let replace_string = or_string.replacen("INSERT INTO", "INSERT OR REPLACE INTO", 1);
+
+    quote! {
+        /// Adds the struct, or updates the columns of the row it conflicts with on the `#[table(unique(...))]` key, the
+        /// only `#[unique]` field or else the primary key. Returns the number of rows added or updated.
+        pub fn upsert(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let query_string: &str = #upsert_string;
+            let stmt: usize = conn.prepare_cached(query_string)?.execute(rusqlite::params![#( self.#upsert_idents),*])?;
+            return Ok(stmt);
+        }
+
+        /// Adds the struct unless it breaks a constraint, returns 0 when it wasn't added.
+        pub fn insert_or_ignore(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let query_string: &str = #ignore_string;
+            let stmt: usize = conn.prepare_cached(query_string)?.execute(rusqlite::params![#( self.#or_idents),*])?;
+            return Ok(stmt);
+        }
+
+        /// Adds the struct, deleting the rows it conflicts with on a PRIMARY KEY or UNIQUE constraint first.
+        pub fn insert_or_replace(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let query_string: &str = #replace_string;
+            let stmt: usize = conn.prepare_cached(query_string)?.execute(rusqlite::params![#( self.#or_idents),*])?;
+            return Ok(stmt);
+        }
+    }
+}
+
+fn body_update(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let table_name = quote_identifier(&table.table_name);
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+    let non_primary_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !is_key(f, &primary_fields)).collect();
+
+    let primary_idents: Vec<&Ident> = primary_fields.iter().map(|f| f.ident).collect();
+    let non_primary_idents: Vec<&Ident> = non_primary_fields.iter().map(|f| f.ident).collect();
+
+    // SET values are bound first, followed by the key values used in the WHERE clause
+    let up_strings: Vec<String> = non_primary_fields.iter()
+        .enumerate()
+        .map(|(index, f)| format!("{} = ?{}", quote_identifier(&f.column_name), index + 1)).collect();
+    let where_strings: Vec<String> = primary_fields.iter()
+        .enumerate()
+        .map(|(index, f)| format!("{} = ?{}", quote_identifier(&f.column_name), non_primary_fields.len() + index + 1)).collect();
+
+    let joined_up_strings: String = up_strings.join(",\n");
+    let joined_where_strings: String = where_strings.join(" AND ");
+    let query_string: String = format!("UPDATE {} SET {} WHERE {};", table_name, joined_up_strings, joined_where_strings);
+    let returning_string: String = returning_string(&query_string, fields);
+    let select_string: String = select_string(fields);
+
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let patch_struct: &Ident = &format_ident!("{}Patch", struct_name);
+    let patch_assignments = patch_assignments(fields, &quote!(patch));
+    let (key_type, key_idents, bind_key) = key_tokens(&primary_fields);
+    let key_conditions: Vec<String> = primary_fields.iter().map(|f| format!("AND {} = ", quote_identifier(&f.column_name))).collect();
+
+    // With every column in the key there is nothing for `update` to set.
+    let update_fn_tokens = (!non_primary_fields.is_empty()).then(|| quote! {
+        pub fn update(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let query_string: &str = #query_string;
+            let stmt: usize = conn.prepare_cached(query_string)?.execute(rusqlite::params![#( self.#non_primary_idents,)* #( self.#primary_idents),*])?;  
+            return Ok(stmt);
+        }
+
+        /// Same as `update`, returning the row as stored. Fails with `Error::NotFound` when no row has the struct's primary key.
+        pub fn update_returning(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<Self> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let mut obj_vector = Self::returning_rows(conn, #returning_string, rusqlite::params![#( self.#non_primary_idents,)* #( self.#primary_idents),*])?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+    });
+
+    quote! {
+        #update_fn_tokens
+
+        /// Sets the `Some` fields of `patch` on the row with the primary key `key`, a tuple when the key is composite.
+        pub fn update_partial(conn: &impl ::sqlite_helper::Executor, key: #key_type, patch: #patch_struct) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            #bind_key
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let mut set_strings: Vec<String> = Vec::new();
+            #patch_assignments
+            if set_strings.is_empty() {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("update_partial needs at least one Some field in the patch")));
+            }
+
+            let mut query_string: String = format!("UPDATE {} SET {} WHERE TRUE = TRUE", #table_name, set_strings.join(", "));
+            #(
+                to_sql_objects.push(#key_idents);
+                query_string = format!("{}\n{}?", query_string, #key_conditions);
+            )*
+            let stmt: usize = conn.execute(&query_string, rusqlite::params_from_iter(to_sql_objects))?;
+            return Ok(stmt);
+        }
+
+        /// Sets the `Some` fields of `patch` on every row matching `filter`, returning the number of rows changed.
+        pub fn update_where(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>, patch: #patch_struct) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let mut set_strings: Vec<String> = Vec::new();
+            #patch_assignments
+            if set_strings.is_empty() {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("update_where needs at least one Some field in the patch")));
+            }
+
+            let query_string: String = format!("UPDATE {} SET {} WHERE {}", #table_name, set_strings.join(", "), filter.where_clause(&mut to_sql_objects));
+            let stmt: usize = conn.execute(&query_string, rusqlite::params_from_iter(to_sql_objects))?;
+            return Ok(stmt);
+        }
+
+        /// Same as `update_where`, returning the changed rows as stored.
+        pub fn update_where_returning(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>, patch: #patch_struct) -> ::sqlite_helper::Result<Vec<Self>> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let mut set_strings: Vec<String> = Vec::new();
+            #patch_assignments
+            if set_strings.is_empty() {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("update_where_returning needs at least one Some field in the patch")));
+            }
+
+            let query_string: String = format!("UPDATE {} SET {} WHERE {} RETURNING {}", #table_name, set_strings.join(", "), filter.where_clause(&mut to_sql_objects), #select_string);
+            return Self::returning_rows(conn, &query_string, rusqlite::params_from_iter(to_sql_objects));
+        }
+    }
+}
+
+fn body_delete(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+
+    let table_name = quote_identifier(&table.table_name);
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+
+    let primary_idents: Vec<&Ident> = primary_fields.iter().map(|f| f.ident).collect();
+    let where_strings: Vec<String> = primary_fields.iter()
+        .enumerate()
+        .map(|(index, f)| format!("{} = ?{}", quote_identifier(&f.column_name), index + 1)).collect();
+    let query_string = format!("DELETE FROM {} WHERE {};", table_name, where_strings.join(" AND "));
+    let returning_string: String = returning_string(&query_string, fields);
+    let select_string: String = select_string(fields);
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    quote! {
+        pub fn delete(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let query_string: &str = #query_string;
+            let stmt: usize = conn.prepare_cached(query_string)?.execute(rusqlite::params![#( self.#primary_idents),*])?;
+            return Ok(stmt);
+        }
+
+        /// Same as `delete`, returning the row as it was before being deleted. Fails with `Error::NotFound` when no row has the
+        /// struct's primary key.
+        pub fn delete_returning(&self, conn: &impl ::sqlite_helper::Executor) -> ::sqlite_helper::Result<Self> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let mut obj_vector = Self::returning_rows(conn, #returning_string, rusqlite::params![#( self.#primary_idents),*])?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+
+        /// Deletes every row matching `filter`, an empty request deletes the whole table.
+        pub fn delete_where(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<usize> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let query_string: String = format!("DELETE FROM {} WHERE {}", #table_name, filter.where_clause(&mut to_sql_objects));
+            let stmt: usize = conn.execute(&query_string, rusqlite::params_from_iter(to_sql_objects))?;
+            return Ok(stmt);
+        }
+
+        /// Same as `delete_where`, returning the deleted rows.
+        pub fn delete_where_returning(conn: &impl ::sqlite_helper::Executor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Vec<Self>> {
+            let conn = ::sqlite_helper::Executor::connection(conn);
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<&dyn rusqlite::ToSql> = Vec::new();
+            let query_string: String = format!("DELETE FROM {} WHERE {} RETURNING {}", #table_name, filter.where_clause(&mut to_sql_objects), #select_string);
+            return Self::returning_rows(conn, &query_string, rusqlite::params_from_iter(to_sql_objects));
+        }
+    }
+}
+
+#[proc_macro_derive(LibSqlQueryable, attributes(primary, unique, table, column))]
+pub fn libsql_macro(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let table = match parse_table(&ast) {
+        Ok(table) => table,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+    let fields_named = match named_fields(&ast, "LibSqlQueryable") {
+        Ok(fields_named) => fields_named,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+    let struct_name = &ast.ident;
+    let fields: Vec<FieldAttribute> = match check_fields(fields_named, struct_name, &table) {
+        Ok(fields) => fields,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let request = request_struct(&fields, struct_name);
+    let filter = filter_enum(struct_name);
+    let field = field_enum(&fields, struct_name);
+    let where_clause = filter_where_clause(struct_name, quote!(libsql_client::Value), libsql_request_conditions(&fields));
+    let patch = patch_struct(&fields, struct_name);
+    let get_fn_tokens = libsql_body_get(&fields, struct_name, &table);
+    let page_fn_tokens = libsql_body_page(&fields, struct_name, &table);
+    let aggregate_fn_tokens = libsql_body_aggregate(&fields, struct_name, &table);
+    let add_fn_tokens = libsql_body_add(&fields, &table);
+    let update_fn_tokens = libsql_body_update(&fields, struct_name, &table);
+    let upsert_fn_tokens = libsql_body_upsert(&fields, &table);
+    let delete_fn_tokens = libsql_body_delete(&fields, struct_name, &table);
+    let create_table_fn_tokens = libsql_body_create_table(&fields, &table);
+    let new_functions = quote! {
+        #request
+        #filter
+        #where_clause
+        #field
+        #patch
+
+        impl #struct_name {
+            #create_table_fn_tokens
+            #get_fn_tokens
+            #page_fn_tokens
+            #aggregate_fn_tokens
+            #add_fn_tokens
+            #upsert_fn_tokens
+            #update_fn_tokens
+            #delete_fn_tokens
+        }
+    };
+
+    if let Err(err) = write_debug_output("LibSqlQueryable", &fields, struct_name, &table, &new_functions) {
+        return TokenStream::from(err.to_compile_error());
+    }
+    return TokenStream::from(new_functions);
+}
+
+fn libsql_body_create_table(fields: &[FieldAttribute], table: &TableAttribute) -> proc_macro2::TokenStream {
+    let (create_string, create_if_not_exists_string) = match create_table_strings(fields, table) {
+        Ok(strings) => strings,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    quote! {
+        pub fn create_table_sql() -> &'static str {
+            #create_string
+        }
+
+        pub fn create_table_if_not_exists_sql() -> &'static str {
+            #create_if_not_exists_string
+        }
+
+        pub async fn create_table(client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<()> {
+            ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::new(Self::create_table_sql())).await?;
+            return Ok(());
+        }
+
+        pub async fn create_table_if_not_exists(client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<()> {
+            ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::new(Self::create_table_if_not_exists_sql())).await?;
+            return Ok(());
+        }
+    }
+}
+
+fn libsql_body_get(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let field_enum: &Ident = &format_ident!("{}Field", struct_name);
+    let table_name = quote_identifier(&table.table_name);
+    let select_string: String = select_string(fields);
+    let key_variants: Vec<Ident> = primary_fields(fields).iter().map(|f| field_variant(f)).collect();
+
+    quote! {
+        /// Returns the only row matching `filter`, failing with `Error::NotFound` or `Error::NotUnique` otherwise.
+        pub async fn get(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Self> {
+            return Self::get_optional(client, filter).await?.ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+
+        /// Same as `get`, with `None` when no row matches.
+        pub async fn get_optional(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<Self>> {
+            let mut obj_vector = Self::get_many_with(client, filter, ::sqlite_helper::QueryOptions::new().limit(2)).await?;
+            if obj_vector.len() > 1 {
+                return Err(::sqlite_helper::Error::NotUnique);
+            }
+            return Ok(obj_vector.pop());
+        }
+
+        /// Returns the first row matching `filter` in primary key order, failing with `Error::NotFound` when none does.
+        pub async fn get_first(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Self> {
+            let options = ::sqlite_helper::QueryOptions::new()#(.asc(#field_enum::#key_variants))*.limit(1);
+            let mut obj_vector = Self::get_many_with(client, filter, options).await?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+
+        pub async fn get_many(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Vec<Self>> {
+            return Self::get_many_with(client, filter, ::sqlite_helper::QueryOptions::new()).await;
+        }
+
+        /// Same as `get_many`, with the `ORDER BY`, `LIMIT` and `OFFSET` set in `options`.
+        pub async fn get_many_with(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>, options: ::sqlite_helper::QueryOptions<#field_enum>) -> ::sqlite_helper::Result<Vec<Self>> {
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let query_string: String = format!("SELECT {} FROM {} WHERE {}{}", #select_string, #table_name, filter.where_clause(&mut to_sql_objects), options.sql());
+
+            let obj_vector = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?
+                .rows
+                .iter()
+                .map(|row| libsql_client::de::from_row(row).map_err(::sqlite_helper::Error::conversion))
+                .collect::<Result<Vec<#struct_name>, _>>()?;
+
+            return Ok(obj_vector);
+        }
+
+        /// Runs a statement with a `RETURNING` clause and reads back the rows it returns.
+        async fn returning_rows(client: &impl ::sqlite_helper::LibSqlExecutor, statement: libsql_client::Statement) -> ::sqlite_helper::Result<Vec<Self>> {
+            let obj_vector = ::sqlite_helper::LibSqlExecutor::execute(client, statement).await?
+                .rows
+                .iter()
+                .map(|row| libsql_client::de::from_row(row).map_err(::sqlite_helper::Error::conversion))
+                .collect::<Result<Vec<#struct_name>, _>>()?;
+
+            return Ok(obj_vector);
+        }
+    }
+}
+
+/// Keyset pagination, see `body_page`.
+fn libsql_body_page(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let field_enum: &Ident = &format_ident!("{}Field", struct_name);
+    let table_name = quote_identifier(&table.table_name);
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+    let key_variants: Vec<Ident> = primary_fields.iter().map(|f| field_variant(f)).collect();
+    let variants: Vec<Ident> = fields.iter().map(field_variant).collect();
+    let idents: Vec<&Ident> = fields.iter().map(|f| f.ident).collect();
+    let select_string: String = select_string(fields);
+
+    quote! {
+        /// Streams the rows matching `filter` in primary key order, loading `page_size` rows at a time with `page_after`.
+        /// The stream ends after the first error.
+        pub fn stream<'a>(client: &'a impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>, page_size: u64) -> impl ::sqlite_helper::stream::Stream<Item = ::sqlite_helper::Result<Self>> + 'a {
+            let filter: #filter_enum = filter.into();
+            let state = (filter, None, std::collections::VecDeque::new(), false);
+            return ::sqlite_helper::stream::unfold(state, move |(filter, cursor, mut rows, done): (#filter_enum, Option<::sqlite_helper::Cursor>, std::collections::VecDeque<Self>, bool)| async move {
+                if let Some(row) = rows.pop_front() {
+                    return Some((Ok(row), (filter, cursor, rows, done)));
+                }
+                if done {
+                    return None;
+                }
+                match Self::page_after(client, filter.clone(), cursor.as_ref(), page_size).await {
+                    Ok(page) => {
+                        let mut rows: std::collections::VecDeque<Self> = page.rows.into();
+                        let done = page.next_cursor.is_none();
+                        let row = rows.pop_front()?;
+                        Some((Ok(row), (filter, page.next_cursor, rows, done)))
+                    },
+                    Err(err) => Some((Err(err), (filter, cursor, rows, true))),
+                }
+            });
+        }
+
+        /// Returns up to `limit` rows matching `filter` in primary key order, starting after `cursor`.
+        pub async fn page_after(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>, cursor: Option<&::sqlite_helper::Cursor>, limit: u64) -> ::sqlite_helper::Result<::sqlite_helper::Page<Self>> {
+            return Self::page_after_by(client, filter, &[], cursor, limit).await;
+        }
+
+        /// Same as `page_after`, ordered by `columns` and then by the primary key. NULLs sort before every other value.
+        pub async fn page_after_by(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>, columns: &[#field_enum], cursor: Option<&::sqlite_helper::Cursor>, limit: u64) -> ::sqlite_helper::Result<::sqlite_helper::Page<Self>> {
+            if limit == 0 {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("page_after needs a limit above 0")));
+            }
+            let mut columns: Vec<#field_enum> = columns.to_vec();
+            for key in &[#(#field_enum::#key_variants),*] {
+                if !columns.contains(key) {
+                    columns.push(*key);
+                }
+            }
+
+            let filter: #filter_enum = filter.into();
+            let cursor_values: Vec<::sqlite_helper::CursorValue> = match cursor {
+                Some(cursor) => cursor.decode()?,
+                None => Vec::new(),
+            };
+            if cursor.is_some() && cursor_values.len() != columns.len() {
+                return Err(::sqlite_helper::Error::InvalidCursor);
+            }
+            let cursor_params: Vec<libsql_client::Value> = cursor_values.iter().cloned().map(|value| match value {
+                ::sqlite_helper::CursorValue::Null => libsql_client::Value::Null,
+                ::sqlite_helper::CursorValue::Integer(value) => libsql_client::Value::Integer { value },
+                ::sqlite_helper::CursorValue::Real(value) => libsql_client::Value::Float { value },
+                ::sqlite_helper::CursorValue::Text(value) => libsql_client::Value::Text { value },
+                ::sqlite_helper::CursorValue::Blob(value) => libsql_client::Value::Blob { value },
+            }).collect();
+
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let mut query_string: String = format!("SELECT {} FROM {} WHERE {}", #select_string, #table_name, filter.where_clause(&mut to_sql_objects));
+            let mut options = ::sqlite_helper::QueryOptions::new().limit(limit.saturating_add(1));
+            for column in &columns {
+                options = options.asc(*column);
+            }
+            if !cursor_values.is_empty() {
+                let column_names: Vec<&str> = columns.iter().map(::sqlite_helper::Column::column).collect();
+                let (condition, params) = ::sqlite_helper::after_condition(&column_names, &cursor_values);
+                query_string = format!("{} AND {}", query_string, condition);
+                for index in params {
+                    to_sql_objects.push(cursor_params[index].clone());
+                }
+            }
+            query_string = format!("{}{}", query_string, options.sql());
+
+            let mut rows: Vec<Self> = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?
+                .rows
+                .iter()
+                .map(|row| libsql_client::de::from_row(row).map_err(::sqlite_helper::Error::conversion))
+                .collect::<Result<Vec<#struct_name>, _>>()?;
+
+            let mut next_cursor: Option<::sqlite_helper::Cursor> = None;
+            if rows.len() as u64 > limit {
+                rows.truncate(limit as usize);
+                if let Some(last) = rows.last() {
+                    let values: Vec<::sqlite_helper::CursorValue> = columns.iter().map(|column| last.cursor_value(*column)).collect();
+                    next_cursor = Some(::sqlite_helper::Cursor::encode(&values));
+                }
+            }
+            return Ok(::sqlite_helper::Page { rows, next_cursor });
+        }
+
+        fn cursor_value(&self, column: #field_enum) -> ::sqlite_helper::CursorValue {
+            let value: libsql_client::Value = match column {
+                #(#field_enum::#variants => self.#idents.clone().into()),*
+            };
+            return match value {
+                libsql_client::Value::Null => ::sqlite_helper::CursorValue::Null,
+                libsql_client::Value::Integer { value } => ::sqlite_helper::CursorValue::Integer(value),
+                libsql_client::Value::Float { value } => ::sqlite_helper::CursorValue::Real(value),
+                libsql_client::Value::Text { value } => ::sqlite_helper::CursorValue::Text(value),
+                libsql_client::Value::Blob { value } => ::sqlite_helper::CursorValue::Blob(value),
+            };
+        }
+    }
+}
+
+/// `count`, `exists` and the typed aggregates, see `body_aggregate`.
+fn libsql_body_aggregate(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let table_name = quote_identifier(&table.table_name);
+    let aggregates: Vec<proc_macro2::TokenStream> = fields.iter().filter_map(|f| {
+        let (ty, is_integer) = numeric_type(&f.field.ty)?;
+        let column = quote_identifier(&f.column_name);
+        let sum_type = if is_integer { quote!(i64) } else { quote!(f64) };
+        let sum = format_ident!("sum_{}", f.ident_name);
+        let min = format_ident!("min_{}", f.ident_name);
+        let max = format_ident!("max_{}", f.ident_name);
+        let avg = format_ident!("avg_{}", f.ident_name);
+        let (sum_value, value) = if is_integer {
+            (quote!(Self::integer_aggregate(value, "SUM")?), quote!(<#ty as std::convert::TryFrom<i64>>::try_from(Self::integer_aggregate(value, function)?)?))
+        } else {
+            (quote!(Self::real_aggregate(value, "SUM")?), quote!(Self::real_aggregate(value, function)? as #ty))
+        };
+        Some(quote! {
+            /// `None` when no row matches `filter` or every value is NULL.
+            pub async fn #sum(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<#sum_type>> {
+                return match Self::aggregate(client, "SUM", #column, filter.into()).await? {
+                    libsql_client::Value::Null => Ok(None),
+                    value => Ok(Some(#sum_value)),
+                };
+            }
+
+            pub async fn #min(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<#ty>> {
+                let function = "MIN";
+                return match Self::aggregate(client, function, #column, filter.into()).await? {
+                    libsql_client::Value::Null => Ok(None),
+                    value => Ok(Some(#value)),
+                };
+            }
+
+            pub async fn #max(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<#ty>> {
+                let function = "MAX";
+                return match Self::aggregate(client, function, #column, filter.into()).await? {
+                    libsql_client::Value::Null => Ok(None),
+                    value => Ok(Some(#value)),
+                };
+            }
+
+            pub async fn #avg(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Option<f64>> {
+                return match Self::aggregate(client, "AVG", #column, filter.into()).await? {
+                    libsql_client::Value::Null => Ok(None),
+                    value => Ok(Some(Self::real_aggregate(value, "AVG")?)),
+                };
+            }
+        })
+    }).collect();
+    // Only the numeric field aggregates read reals, leaving it out otherwise avoids an unused function.
+    let real_aggregate = if aggregates.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            fn real_aggregate(value: libsql_client::Value, function: &str) -> ::sqlite_helper::Result<f64> {
+                return match value {
+                    libsql_client::Value::Integer { value } => Ok(value as f64),
+                    libsql_client::Value::Float { value } => Ok(value),
+                    other => Err(::sqlite_helper::Error::conversion(format!("{} returned {:?}, not a number", function, other))),
+                };
+            }
+        })
+    };
+
+    quote! {
+        /// Counts the rows matching `filter` without loading them.
+        pub async fn count(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<u64> {
+            let count = Self::integer_aggregate(Self::aggregate(client, "COUNT", "*", filter.into()).await?, "COUNT")?;
+            return Ok(count as u64);
+        }
+
+        /// Whether any row matches `filter`.
+        pub async fn exists(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<bool> {
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let query_string: String = format!("SELECT EXISTS(SELECT 1 FROM {} WHERE {})", #table_name, filter.where_clause(&mut to_sql_objects));
+            let result = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?;
+            let value = result.rows.first().and_then(|row| row.values.first()).cloned().unwrap_or(libsql_client::Value::Null);
+            return Ok(Self::integer_aggregate(value, "EXISTS")? != 0);
+        }
+
+        async fn aggregate(client: &impl ::sqlite_helper::LibSqlExecutor, function: &str, column: &str, filter: #filter_enum) -> ::sqlite_helper::Result<libsql_client::Value> {
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let query_string: String = format!("SELECT {}({}) FROM {} WHERE {}", function, column, #table_name, filter.where_clause(&mut to_sql_objects));
+            let result = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?;
+            return Ok(result.rows.first().and_then(|row| row.values.first()).cloned().unwrap_or(libsql_client::Value::Null));
+        }
+
+        fn integer_aggregate(value: libsql_client::Value, function: &str) -> ::sqlite_helper::Result<i64> {
+            return match value {
+                libsql_client::Value::Integer { value } => Ok(value),
+                other => Err(::sqlite_helper::Error::conversion(format!("{} returned {:?}, not an integer", function, other))),
+            };
+        }
+
+        #real_aggregate
+
+        #(#aggregates)*
+    }
+}
+
+/// Pushes a condition onto `conditions` for every `Some` filter in `request`, pushing its values onto `to_sql_objects`.
+fn libsql_request_conditions(fields: &[FieldAttribute]) -> proc_macro2::TokenStream {
+    let idents: Vec<&Ident> = fields.iter().map(|f| f.ident).collect();
+    let columns: Vec<String> = fields.iter().map(|f| quote_identifier(&f.column_name)).collect();
+
+    quote! {
+        #(
+            if let Some(filter) = &request.#idents {
+                let mut params = Vec::new();
+                conditions.push(filter.condition(#columns, &mut params));
+                for param in params {
+                    match param {
+                        ::sqlite_helper::Param::Value(value) => to_sql_objects.push(value.clone().into()),
+                        ::sqlite_helper::Param::Text(text) => to_sql_objects.push(text.clone().into()),
+                    }
+                }
+            }
+        )*
+    }
+}
+
+/// Pushes a `column = ?` assignment onto `set_strings` for every `Some` field in `patch`, pushing its value onto `to_sql_objects`.
+fn libsql_patch_assignments(fields: &[FieldAttribute], patch: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let idents: Vec<&Ident> = fields.iter().map(|f| f.ident).collect();
+    let assignments: Vec<String> = fields.iter().map(|f| format!("{} = ?", quote_identifier(&f.column_name))).collect();
+
+    quote! {
+        #(
+            if let Some(value) = &#patch.#idents {
+                set_strings.push(#assignments.to_string());
+                to_sql_objects.push(value.clone().into());
+            }
+        )*
+    }
+}
+
+fn libsql_body_delete(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let table_name = quote_identifier(&table.table_name);
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+
+    let primary_idents: Vec<&Ident> = primary_fields.iter().map(|f| f.ident).collect();
+    let where_strings: Vec<String> = primary_fields.iter().map(|f| format!("{} = ?", quote_identifier(&f.column_name))).collect();
+    let query_string = format!("DELETE FROM {} WHERE {};", table_name, where_strings.join(" AND "));
+    let returning_string: String = returning_string(&query_string, fields);
+    let select_string: String = select_string(fields);
+
+    quote! {
+        pub async fn delete(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<usize> {
+            let query_string: &str = #query_string;
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#primary_idents.clone().into());)*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(query_string, &params)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Same as `delete`, returning the row as it was before being deleted. Fails with `Error::NotFound` when no row has the
+        /// struct's primary key.
+        pub async fn delete_returning(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<Self> {
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#primary_idents.clone().into());)*
+            let mut obj_vector = Self::returning_rows(client, libsql_client::Statement::with_args(#returning_string, &params)).await?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+
+        /// Deletes every row matching `filter`, an empty request deletes the whole table.
+        pub async fn delete_where(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<usize> {
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let query_string: String = format!("DELETE FROM {} WHERE {}", #table_name, filter.where_clause(&mut to_sql_objects));
+
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Same as `delete_where`, returning the deleted rows.
+        pub async fn delete_where_returning(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<Vec<Self>> {
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let query_string: String = format!("DELETE FROM {} WHERE {} RETURNING {}", #table_name, filter.where_clause(&mut to_sql_objects), #select_string);
+            return Self::returning_rows(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await;
+        }
+
+        /// Same as `delete_where`.
+        pub async fn delete_many(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>) -> ::sqlite_helper::Result<usize> {
+            return Self::delete_where(client, filter).await;
+        }
+    }
+}
+
+struct FieldAttribute<'a> {
+    pub is_primary: bool,
+    pub is_autoincrement: bool,
+    pub is_unique: bool,
+    pub field: &'a Field,
+    pub ident: &'a Ident,
+    pub ident_name: String,
+    pub column_name: String
+}
+
+fn parse_field<'a>(field: &'a Field, table: &TableAttribute) -> syn::Result<FieldAttribute<'a>> {
+    let mut is_primary = false;
+    let mut is_autoincrement = false;
+    let mut is_unique = false;
+    let mut column_name: Option<String> = None;
+    for attr in &field.attrs {
+        if attr.path().is_ident("primary") {
+            if is_primary {
+                return Err(syn::Error::new_spanned(attr, "duplicate #[primary] attribute"));
+            }
+            is_primary = true;
+            // A bare #[primary] has no options to parse.
+            if let syn::Meta::Path(_) = attr.meta {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("autoincrement") {
+                    if is_autoincrement {
+                        return Err(meta.error("duplicate `autoincrement` option"));
+                    }
+                    is_autoincrement = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `autoincrement`"))
+                }
+            })?;
+        } else if attr.path().is_ident("unique") {
+            if is_unique {
+                return Err(syn::Error::new_spanned(attr, "duplicate #[unique] attribute"));
+            }
+            if !matches!(attr.meta, syn::Meta::Path(_)) {
+                return Err(syn::Error::new_spanned(&attr.meta, "#[unique] takes no options"));
+            }
+            is_unique = true;
+        } else if attr.path().is_ident("column") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    if column_name.is_some() {
+                        return Err(meta.error("duplicate column name"));
+                    }
+                    let value: LitStr = meta.value()?.parse()?;
+                    column_name = Some(value.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name`"))
+                }
+            })?;
+        }
+    }
+
+    let ident = field.ident.as_ref().ok_or_else(|| syn::Error::new_spanned(field, "expected a named field"))?;
+    let ident_name = ident.unraw().to_string();
+    let column_name = column_name.unwrap_or_else(|| match table.rename_all {
+        Some(rule) => rule.apply(&ident_name),
+        None => ident_name.clone(),
+    });
+
+    return Ok(FieldAttribute {
+        is_primary,
+        is_autoincrement,
+        is_unique,
+        field,
+        ident,
+        ident_name,
+        column_name
+    });
+}
+
+/// Wraps a table or column name in double quotes so keywords like `order` or `group` can be used as names.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// The INSERT statement for `fields`, with `placeholder` giving the parameter for the nth value.
+fn insert_string(fields: &[&FieldAttribute], table: &TableAttribute, placeholder: impl Fn(usize) -> String) -> String {
+    if fields.is_empty() {
+        return format!("INSERT INTO {} DEFAULT VALUES;", quote_identifier(&table.table_name));
+    }
+
+    let vals: Vec<String> = (0..fields.len()).map(placeholder).collect();
+    format!("{} VALUES ({});", insert_prefix(fields, table), vals.join(", "))
+}
+
+/// `query_string` with a `RETURNING` clause reading back every field, like `select_string` does for a SELECT.
+fn returning_string(query_string: &str, fields: &[FieldAttribute]) -> String {
+    format!("{} RETURNING {};", query_string.trim_end_matches(';'), select_string(fields))
+}
+
+/// The INSERT statement for `fields` up to its `VALUES`, like `INSERT INTO "table" ("a", "b")`.
+fn insert_prefix(fields: &[&FieldAttribute], table: &TableAttribute) -> String {
+    let var_strings: Vec<String> = fields.iter().map(|f| quote_identifier(&f.column_name)).collect();
+    format!("INSERT INTO {} ({})", quote_identifier(&table.table_name), var_strings.join(", "))
+}
+
+/// Statements assigning `rowid` to the autoincrement field, `None` when the struct doesn't have one.
+fn set_autoincrement_tokens(fields: &[FieldAttribute]) -> Option<proc_macro2::TokenStream> {
+    let field = fields.iter().find(|f| f.is_autoincrement)?;
+    let ident = field.ident;
+    Some(match option_inner_type(&field.field.ty) {
+        Some(inner) => quote! {
+            self.#ident = Some(<#inner as std::convert::TryFrom<i64>>::try_from(rowid)?);
+        },
+        None => {
+            let ty = &field.field.ty;
+            quote! {
+                self.#ident = <#ty as std::convert::TryFrom<i64>>::try_from(rowid)?;
+            }
+        },
+    })
+}
+
+/// The column list for a SELECT. Rows are read back by field name, so renamed columns are aliased to their field.
+fn select_string(fields: &[FieldAttribute]) -> String {
+    let select_strings: Vec<String> = fields.iter().map(|f| {
+        if f.column_name == f.ident_name {
+            quote_identifier(&f.column_name)
+        } else {
+            format!("{} AS {}", quote_identifier(&f.column_name), quote_identifier(&f.ident_name))
+        }
+    }).collect();
+    select_strings.join(", ")
+}
+
+/// With `#[table(debug)]` on the struct, or `DATABASE_MACROS_DEBUG` set for every struct, writes the code generated for
+/// the struct and its `CREATE TABLE` statement to `OUT_DIR`, or `target/database_macros` when the crate has no build script.
+fn write_debug_output(derive: &str, fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute, tokens: &proc_macro2::TokenStream) -> syn::Result<()> {
+    let from_env = matches!(std::env::var("DATABASE_MACROS_DEBUG").as_deref(), Ok(value) if !value.is_empty() && value != "0");
+    if !table.debug && !from_env {
+        return Ok(());
+    }
+
+    let directory = match std::env::var_os("OUT_DIR") {
+        Some(out_dir) => std::path::PathBuf::from(out_dir),
+        None => {
+            let manifest_dir = std::path::PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+            let target_dir = std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| manifest_dir.join("target"), |target_dir| manifest_dir.join(target_dir));
+            target_dir.join("database_macros")
+        },
+    };
+    let (create_string, _) = create_table_strings(fields, table)?;
+    let contents = format!("// #[derive({})] for {}\n// {}\n\n{}\n", derive, struct_name, create_string, tokens);
+
+    let path = directory.join(format!("{}.rs", struct_name));
+    std::fs::create_dir_all(&directory)
+        .and_then(|_| std::fs::write(&path, contents))
+        .map_err(|err| syn::Error::new(struct_name.span(), format!("couldn't write the debug output to {}: {}", path.display(), err)))
+}
+
+/// SQLite can represent any name in a quoted identifier except an empty one or one containing a NUL.
+fn check_identifier(name: &str, span: proc_macro2::Span) -> syn::Result<()> {
+    if name.is_empty() {
+        return Err(syn::Error::new(span, "SQLite identifiers can not be empty"));
+    }
+    if name.contains('\0') {
+        return Err(syn::Error::new(span, format!("SQLite identifier `{}` can not contain a NUL character", name.escape_default())));
+    }
+    Ok(())
+}
+
+/// Parses and validates the field attributes and column names up front, so the generators can rely on them.
+/// Every problem found is reported rather than only the first one.
+fn check_fields<'a>(fields_named: &'a FieldsNamed, struct_name: &Ident, table: &TableAttribute) -> syn::Result<Vec<FieldAttribute<'a>>> {
+    let mut errors: Vec<syn::Error> = Vec::new();
+    let mut fields: Vec<FieldAttribute> = Vec::new();
+    for field in &fields_named.named {
+        match parse_field(field, table) {
+            Ok(field_attribute) => fields.push(field_attribute),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    for (index, field) in fields.iter().enumerate() {
+        if let Err(err) = check_identifier(&field.column_name, field.ident.span()) {
+            errors.push(err);
+        }
+        if let Some(other) = fields[..index].iter().find(|other| other.column_name == field.column_name) {
+            errors.push(syn::Error::new(field.ident.span(), format!("column `{}` is already used by `{}`", field.column_name, other.ident_name)));
+        }
+        // `field_variant` builds the `{Struct}Field` variants with `format_ident!`, which panics on a name that isn't an identifier.
+        let variant = RenameRule::Pascal.apply(&field.ident_name);
+        if syn::parse_str::<Ident>(&variant).is_err() {
+            errors.push(syn::Error::new(field.ident.span(), format!("`{}` has no {}Field variant, `{}` is not an identifier", field.ident_name, struct_name, variant)));
+        } else if let Some(other) = fields[..index].iter().find(|other| RenameRule::Pascal.apply(&other.ident_name) == variant) {
+            errors.push(syn::Error::new(field.ident.span(), format!("`{}` and `{}` are both the {}Field variant `{}`", other.ident_name, field.ident_name, struct_name, variant)));
+        }
+    }
+
+    let autoincrement_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| f.is_autoincrement).collect();
+    for field in &autoincrement_fields {
+        if !matches!(numeric_type(&field.field.ty), Some((_, true))) {
+            errors.push(syn::Error::new_spanned(&field.field.ty, "#[primary(autoincrement)] needs an integer field"));
+        }
+    }
+    if let Some(field) = autoincrement_fields.get(1) {
+        errors.push(syn::Error::new(field.ident.span(), "only one field can be #[primary(autoincrement)]"));
+    } else if let Some(field) = autoincrement_fields.first() {
+        if fields.iter().filter(|f| f.is_primary).count() > 1 {
+            errors.push(syn::Error::new(field.ident.span(), "#[primary(autoincrement)] can't be part of a composite primary key"));
+        }
+    }
+
+    for (index, ident) in table.unique.iter().enumerate() {
+        if !fields_named.named.iter().any(|f| f.ident.as_ref() == Some(ident)) {
+            errors.push(syn::Error::new(ident.span(), format!("{} has no field named `{}`", struct_name, ident)));
+        } else if table.unique[..index].contains(ident) {
+            errors.push(syn::Error::new(ident.span(), format!("`{}` is already part of the unique key", ident)));
+        }
+    }
+
+    if fields_named.named.is_empty() {
+        errors.push(syn::Error::new(struct_name.span(), format!("{} has no #[primary] field and no field to use as its primary key", struct_name)));
+    }
+
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut first) => {
+            first.extend(errors);
+            Err(first)
+        },
+        None => Ok(fields),
+    }
+}
+
+/// Container level `#[table(...)]` options.
+struct TableAttribute {
+    pub table_name: String,
+    pub rename_all: Option<RenameRule>,
+    /// The fields of `#[table(unique(...))]`, a UNIQUE constraint over all of them together.
+    pub unique: Vec<Ident>,
+    pub debug: bool
+}
+
+fn parse_table(ast: &DeriveInput) -> syn::Result<TableAttribute> {
+    let mut table_name: Option<String> = None;
+    let mut rename_all: Option<RenameRule> = None;
+    let mut unique: Option<Vec<Ident>> = None;
+    let mut debug = false;
+    for attr in &ast.attrs {
+        if !attr.path().is_ident("table") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                if table_name.is_some() {
+                    return Err(meta.error("duplicate table name"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                table_name = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("rename_all") {
+                if rename_all.is_some() {
+                    return Err(meta.error("duplicate `rename_all` option"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                match RenameRule::from_str(&value.value()) {
+                    Some(rule) => {
+                        rename_all = Some(rule);
+                        Ok(())
+                    },
+                    None => Err(syn::Error::new(value.span(), format!("unknown rename rule `{}`", value.value()))),
+                }
+            } else if meta.path.is_ident("unique") {
+                if unique.is_some() {
+                    return Err(meta.error("duplicate `unique` option"));
+                }
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let idents: Vec<Ident> = content.parse_terminated(Ident::parse, syn::Token![,])?.into_iter().collect();
+                if idents.is_empty() {
+                    return Err(meta.error("`unique` needs at least one field"));
+                }
+                unique = Some(idents);
+                Ok(())
+            } else if meta.path.is_ident("debug") {
+                if debug {
+                    return Err(meta.error("duplicate `debug` option"));
+                }
+                debug = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `name`, `rename_all`, `unique` or `debug`"))
+            }
+        })?;
+    }
+
+    let struct_name = ast.ident.unraw().to_string();
+    let table_name = table_name.unwrap_or_else(|| match rename_all {
+        Some(rule) => rule.apply(&struct_name),
+        None => struct_name,
+    });
+    check_identifier(&table_name, ast.ident.span())?;
+
+    return Ok(TableAttribute {
+        table_name,
+        rename_all,
+        unique: unique.unwrap_or_default(),
+        debug
+    });
+}
+
+/// Casing rules accepted by `#[table(rename_all = "...")]`, spelled the same way serde spells them.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<RenameRule> {
+        match rule {
+            "lowercase" => Some(RenameRule::Lower),
+            "UPPERCASE" => Some(RenameRule::Upper),
+            "PascalCase" => Some(RenameRule::Pascal),
+            "camelCase" => Some(RenameRule::Camel),
+            "snake_case" => Some(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnake),
+            "kebab-case" => Some(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(RenameRule::ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    /// Applies the rule to either a snake_case field name or a PascalCase struct name.
+    fn apply(&self, name: &str) -> String {
+        let words = split_words(name);
+        let capitalize = |word: &String| -> String {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        };
+
+        match self {
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Pascal => words.iter().map(capitalize).collect(),
+            RenameRule::Camel => words.iter()
+                .enumerate()
+                .map(|(i, word)| if i == 0 { word.clone() } else { capitalize(word) })
+                .collect(),
+            RenameRule::Snake => words.join("_"),
+            RenameRule::ScreamingSnake => words.join("_").to_uppercase(),
+            RenameRule::Kebab => words.join("-"),
+            RenameRule::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+/// Splits a name into lowercase words on underscores and case changes, keeping acronyms together.
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(c.to_lowercase());
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// The `#[primary]` fields, or the first field when none is marked. `check_fields` makes sure there is one.
+fn primary_fields<'a, 'b>(fields: &'b [FieldAttribute<'a>]) -> Vec<&'b FieldAttribute<'a>> {
+    let primary_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| f.is_primary).collect();
+    if !primary_fields.is_empty() {
+        return primary_fields;
+    }
+
+    fields.iter().take(1).collect()
+}
+
+/// The fields named by `#[table(unique(...))]` in the order they are listed, empty when the struct doesn't declare one.
+/// `check_fields` makes sure every name is a field.
+fn unique_key_fields<'a, 'b>(fields: &'b [FieldAttribute<'a>], table: &TableAttribute) -> Vec<&'b FieldAttribute<'a>> {
+    table.unique.iter()
+        .filter_map(|ident| fields.iter().find(|f| f.ident == ident))
+        .collect()
+}
+
+fn is_key(field: &FieldAttribute, primary_fields: &[&FieldAttribute]) -> bool {
+    primary_fields.iter().any(|p| p.ident == field.ident)
+}
+        
+
+fn libsql_body_add(fields: &[FieldAttribute], table: &TableAttribute) -> proc_macro2::TokenStream {
+
+    let filtered_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !f.is_autoincrement).collect();
+    let filtered_idents: Vec<&Ident> = filtered_fields.iter().map(|f| f.ident).collect();
+    let select_string: String = select_string(fields);
+    let query_string: String = insert_string(&filtered_fields, table, |_| "?".to_string());
+    let returning_string: String = returning_string(&query_string, fields);
+    // `returning` is the clause the multi-row INSERTs end with, empty for `add_many`.
+    let add_chunks = if filtered_fields.is_empty() {
+        // Only the autoincrement field, which a multi-row INSERT has no way of leaving to its default.
+        quote! {
+            for _ in rows {
+                statements.push(libsql_client::Statement::new(format!("{}{}", #query_string.trim_end_matches(';'), returning)));
+            }
+        }
+    } else {
+        let insert_prefix = insert_prefix(&filtered_fields, table);
+        let column_count = filtered_fields.len();
+        quote! {
+            for chunk in rows.chunks(::sqlite_helper::rows_per_statement(#column_count)) {
+                let query_string: String = format!("{} VALUES {}{}", #insert_prefix, ::sqlite_helper::values_placeholders(chunk.len(), #column_count), returning);
+                let mut params: Vec<libsql_client::Value> = Vec::with_capacity(chunk.len() * #column_count);
+                for row in chunk {
+                    #(params.push(row.#filtered_idents.clone().into());)*
+                }
+                statements.push(libsql_client::Statement::with_args(&query_string, &params));
+            }
+        }
+    };
+    let insert_mut_fn_tokens = set_autoincrement_tokens(fields).map(|set_autoincrement| quote! {
+        /// Adds the struct and writes the id SQLite picked back into its autoincrement field.
+        pub async fn insert_mut(&mut self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<i64> {
+            let rowid: i64 = self.insert(client).await?;
+            #set_autoincrement
+            return Ok(rowid);
+        }
+    });
+    
+    quote! {
+        pub async fn add(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<usize> {
+            let query_string: &str = #query_string;
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#filtered_idents.clone().into());)*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(query_string,  &params)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Adds the struct and returns the rowid of the new row.
+        pub async fn insert(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<i64> {
+            let query_string: &str = #query_string;
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#filtered_idents.clone().into());)*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(query_string,  &params)).await?;
+            return stmt.last_insert_rowid.ok_or_else(|| ::sqlite_helper::Error::Driver("No rowid returned".into()));
+        }
+
+        /// Adds every struct in `rows` with multi-row INSERTs, as many rows per statement as SQLite's parameter limit allows,
+        /// and returns the number of rows added. The INSERTs are sent as one batch, which a `Client` runs in a transaction.
+        pub async fn add_many(client: &impl ::sqlite_helper::LibSqlExecutor, rows: &[Self]) -> ::sqlite_helper::Result<usize> {
+            let returning: &str = "";
+            let mut statements: Vec<libsql_client::Statement> = Vec::new();
+            #add_chunks
+            if statements.is_empty() {
+                return Ok(0);
+            }
+
+            let results = ::sqlite_helper::LibSqlExecutor::batch(client, statements).await?;
+            return Ok(results.iter().map(|result| result.rows_affected as usize).sum());
+        }
+
+        /// Adds the struct and returns the row as stored, with the id SQLite picked and any column defaults.
+        pub async fn add_returning(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<Self> {
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#filtered_idents.clone().into());)*
+            let mut obj_vector = Self::returning_rows(client, libsql_client::Statement::with_args(#returning_string, &params)).await?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+
+        /// Same as `add_many`, returning the rows as stored. SQLite doesn't promise they come back in the order of `rows`.
+        pub async fn add_many_returning(client: &impl ::sqlite_helper::LibSqlExecutor, rows: &[Self]) -> ::sqlite_helper::Result<Vec<Self>> {
+            let returning: String = format!(" RETURNING {}", #select_string);
+            let mut statements: Vec<libsql_client::Statement> = Vec::new();
+            #add_chunks
+            if statements.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let results = ::sqlite_helper::LibSqlExecutor::batch(client, statements).await?;
+            let obj_vector = results.iter()
+                .flat_map(|result| result.rows.iter())
+                .map(|row| libsql_client::de::from_row(row).map_err(::sqlite_helper::Error::conversion))
+                .collect::<Result<Vec<Self>, _>>()?;
+            return Ok(obj_vector);
+        }
+
+        #insert_mut_fn_tokens
+    }
+}
+
+/// `upsert`, `insert_or_ignore` and `insert_or_replace`, see `body_upsert`.
+fn libsql_body_upsert(fields: &[FieldAttribute], table: &TableAttribute) -> proc_macro2::TokenStream {
+    let (upsert_string, upsert_fields, or_string, or_fields) = upsert_strings(fields, table, |_| "?".to_string());
+    let upsert_idents: Vec<&Ident> = upsert_fields.iter().map(|f| f.ident).collect();
+    let or_idents: Vec<&Ident> = or_fields.iter().map(|f| f.ident).collect();
+    let ignore_string = or_string.replacen("INSERT INTO", "INSERT OR IGNORE INTO", 1);
+    let replace_string = or_string.replacen("INSERT INTO", "INSERT OR REPLACE INTO", 1);
+
+    quote! {
+        /// Adds the struct, or updates the columns of the row it conflicts with on the `#[table(unique(...))]` key, the
+        /// only `#[unique]` field or else the primary key. Returns the number of rows added or updated.
+        pub async fn upsert(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<usize> {
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#upsert_idents.clone().into());)*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(#upsert_string, &params)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Adds the struct unless it breaks a constraint, returns 0 when it wasn't added.
+        pub async fn insert_or_ignore(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<usize> {
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#or_idents.clone().into());)*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(#ignore_string, &params)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Adds the struct, deleting the rows it conflicts with on a PRIMARY KEY or UNIQUE constraint first.
+        pub async fn insert_or_replace(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<usize> {
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#or_idents.clone().into());)*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(#replace_string, &params)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+    }
+}
+
+fn libsql_body_update(fields: &[FieldAttribute], struct_name: &Ident, table: &TableAttribute) -> proc_macro2::TokenStream {
+    let table_name = quote_identifier(&table.table_name);
+
+    let primary_fields: Vec<&FieldAttribute> = primary_fields(fields);
+    let non_primary_fields: Vec<&FieldAttribute> = fields.iter().filter(|f| !is_key(f, &primary_fields)).collect();
+
+    let primary_idents: Vec<&Ident> = primary_fields.iter().map(|f| f.ident).collect();
+    let non_primary_idents: Vec<&Ident> = non_primary_fields.iter().map(|f| f.ident).collect();
+
+    let up_strings: Vec<String> = non_primary_fields.iter().map(|f| format!("{} = ?", quote_identifier(&f.column_name))).collect();
+    let where_strings: Vec<String> = primary_fields.iter().map(|f| format!("{} = ?", quote_identifier(&f.column_name))).collect();
+
+
+    let joined_up_strings: String = up_strings.join(",\n");
+    let joined_where_strings: String = where_strings.join(" AND ");
+    let query_string: String = format!("UPDATE {} SET {} WHERE {};", table_name, joined_up_strings, joined_where_strings);
+    let returning_string: String = returning_string(&query_string, fields);
+    let select_string: String = select_string(fields);
+     
+    let filter_enum: &Ident = &format_ident!("{}Filter", struct_name);
+    let patch_struct: &Ident = &format_ident!("{}Patch", struct_name);
+    let patch_assignments = libsql_patch_assignments(fields, &quote!(patch));
+    let (key_type, key_idents, bind_key) = key_tokens(&primary_fields);
+    let key_conditions: Vec<String> = primary_fields.iter().map(|f| format!("AND {} = ", quote_identifier(&f.column_name))).collect();
+
+    // With every column in the key there is nothing for `update` to set.
+    let update_fn_tokens = (!non_primary_fields.is_empty()).then(|| quote! {
+        pub async fn update(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<usize> {
+            let query_string: &str = #query_string;
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#non_primary_idents.clone().into());)*
+            #(params.push(self.#primary_idents.clone().into());)*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(query_string, &params)).await?;  
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Same as `update`, returning the row as stored. Fails with `Error::NotFound` when no row has the struct's primary key.
+        pub async fn update_returning(&self, client: &impl ::sqlite_helper::LibSqlExecutor) -> ::sqlite_helper::Result<Self> {
+            let mut params: Vec<libsql_client::Value> = Vec::new();
+            #(params.push(self.#non_primary_idents.clone().into());)*
+            #(params.push(self.#primary_idents.clone().into());)*
+            let mut obj_vector = Self::returning_rows(client, libsql_client::Statement::with_args(#returning_string, &params)).await?;
+            return obj_vector.pop().ok_or_else(|| ::sqlite_helper::Error::NotFound);
+        }
+    });
+
+    quote! {
+        /// Sets the `Some` fields of `patch` on the row with the primary key `key`, a tuple when the key is composite.
+        pub async fn update_partial(client: &impl ::sqlite_helper::LibSqlExecutor, key: #key_type, patch: #patch_struct) -> ::sqlite_helper::Result<usize> {
+            #bind_key
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let mut set_strings: Vec<String> = Vec::new();
+            #patch_assignments
+            if set_strings.is_empty() {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("update_partial needs at least one Some field in the patch")));
+            }
+
+            let mut query_string: String = format!("UPDATE {} SET {} WHERE TRUE = TRUE", #table_name, set_strings.join(", "));
+            #(
+                to_sql_objects.push(#key_idents.clone().into());
+                query_string = format!("{}\n{}?", query_string, #key_conditions);
+            )*
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Sets the `Some` fields of `patch` on every row matching `filter`, returning the number of rows changed.
+        pub async fn update_where(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>, patch: #patch_struct) -> ::sqlite_helper::Result<usize> {
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let mut set_strings: Vec<String> = Vec::new();
+            #patch_assignments
+            if set_strings.is_empty() {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("update_where needs at least one Some field in the patch")));
+            }
+
+            let query_string: String = format!("UPDATE {} SET {} WHERE {}", #table_name, set_strings.join(", "), filter.where_clause(&mut to_sql_objects));
+            let stmt = ::sqlite_helper::LibSqlExecutor::execute(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await?;
+            return Ok(stmt.rows_affected as usize);
+        }
+
+        /// Same as `update_where`, returning the changed rows as stored.
+        pub async fn update_where_returning(client: &impl ::sqlite_helper::LibSqlExecutor, filter: impl Into<#filter_enum>, patch: #patch_struct) -> ::sqlite_helper::Result<Vec<Self>> {
+            let filter: #filter_enum = filter.into();
+            let mut to_sql_objects: Vec<libsql_client::Value> = Vec::new();
+            let mut set_strings: Vec<String> = Vec::new();
+            #patch_assignments
+            if set_strings.is_empty() {
+                return Err(::sqlite_helper::Error::InvalidArgument(String::from("update_where_returning needs at least one Some field in the patch")));
+            }
+
+            let query_string: String = format!("UPDATE {} SET {} WHERE {} RETURNING {}", #table_name, set_strings.join(", "), filter.where_clause(&mut to_sql_objects), #select_string);
+            return Self::returning_rows(client, libsql_client::Statement::with_args(&query_string, &to_sql_objects)).await;
+        }
+
+        #update_fn_tokens
     }
 }
@@ -0,0 +1,55 @@
+use database_macros::{Queryable, LibSqlQueryable};
+use serde::{Serialize, Deserialize};
+use std::path::PathBuf;
+
+#[derive(Queryable)]
+#[table(name = "debug_models", debug)]
+struct DebugModel {
+    #[primary]
+    pub id: i64,
+    pub name: String
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+#[table(debug)]
+struct LibSqlDebugModel {
+    #[primary(autoincrement)]
+    pub id: i64,
+    pub name: String
+}
+
+/// Where the derives write the debug output, the test crate has no build script so there is no OUT_DIR.
+fn debug_output(struct_name: &str) -> anyhow::Result<String> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = option_env!("CARGO_TARGET_DIR").map_or_else(|| manifest_dir.join("target"), |target_dir| manifest_dir.join(target_dir));
+    let path = target_dir.join("database_macros").join(format!("{}.rs", struct_name));
+    let output = std::fs::read_to_string(path)?;
+    // The tokens are wrapped at arbitrary points, the header lines are kept as they are.
+    let (header, code) = output.split_at(output.find("\n\n").unwrap_or(0));
+    Ok(format!("{}{}", header, code.split_whitespace().collect::<Vec<&str>>().join(" ")))
+}
+
+#[test]
+fn rusqlite_debug_output() -> anyhow::Result<()> {
+    let output = debug_output("DebugModel")?;
+    assert!(output.starts_with("// #[derive(Queryable)] for DebugModel\n"));
+    assert!(output.contains(DebugModel::create_table_sql()));
+    assert!(output.contains("pub fn get_many_with(conn : & impl :: sqlite_helper :: Executor"));
+
+    let conn = rusqlite::Connection::open_in_memory()?;
+    DebugModel::create_table(&conn)?;
+    DebugModel { id: 1, name: String::from("debug") }.add(&conn)?;
+    assert_eq!(DebugModel::get(&conn, DebugModelRequest::default())?.name, "debug");
+
+    return Ok(());
+}
+
+#[test]
+fn libsql_debug_output() -> anyhow::Result<()> {
+    let output = debug_output("LibSqlDebugModel")?;
+    assert!(output.starts_with("// #[derive(LibSqlQueryable)] for LibSqlDebugModel\n"));
+    assert!(output.contains(LibSqlDebugModel::create_table_sql()));
+    assert!(output.contains("pub async fn get_many_with(client : & impl :: sqlite_helper :: LibSqlExecutor"));
+
+    return Ok(());
+}
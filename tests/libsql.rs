@@ -1,6 +1,7 @@
 use database_macros::LibSqlQueryable;
 use serde::{Serialize, Deserialize};
 use anyhow::Context;
+use sqlite_helper::{Filter, QueryOptions};
 
 #[derive(LibSqlQueryable, Serialize, Deserialize)]
 pub struct LibSqlTest {
@@ -12,10 +13,10 @@ pub struct LibSqlTest {
 #[tokio::test]
 async fn get() -> anyhow::Result<()> {
     let db = libsql_client::Client::in_memory().unwrap();
-    db.execute("CREATE TABLE LibSqlTest(id INT PRIMARY KEY, test TEXT NOT NULL);").await?; 
+    LibSqlTest::create_table(&db).await?; 
     db.execute("INSERT INTO LibSqlTest (id, test) VALUES (1, \"test\");").await?;
     let mut req = LibSqlTestRequest::default();
-    req.id = Some(1);
+    req.id = Some(Filter::Eq(1));
     let test_struct = LibSqlTest::get(&db, req).await?;
     
     assert!(test_struct.id == 1);
@@ -28,7 +29,7 @@ async fn get() -> anyhow::Result<()> {
 #[tokio::test]
 async fn get_many() -> anyhow::Result<()> {
     let db = libsql_client::Client::in_memory().unwrap();
-    db.execute("CREATE TABLE LibSqlTest(id INT PRIMARY KEY, test TEXT NOT NULL);").await?; 
+    LibSqlTest::create_table(&db).await?; 
     db.execute("INSERT INTO LibSqlTest (id, test) VALUES (1, \"test\"), (2, \"second_test\");").await?;
     let req = LibSqlTestRequest::default();
     let test_structs = LibSqlTest::get_many(&db, req).await?;
@@ -46,7 +47,7 @@ async fn get_many() -> anyhow::Result<()> {
 #[tokio::test]
 async fn add() -> anyhow::Result<()> {
     let db = libsql_client::Client::in_memory().unwrap();
-    db.execute("CREATE TABLE LibSqlTest(id INT PRIMARY KEY, test TEXT NOT NULL);").await?;
+    LibSqlTest::create_table(&db).await?;
     
     let test_struct = LibSqlTest {
         id: 1,
@@ -57,7 +58,7 @@ async fn add() -> anyhow::Result<()> {
     assert!(result == 1);
     
     let confirm_req = LibSqlTestRequest {
-        id: Some(1), test: None
+        id: Some(Filter::Eq(1)), test: None
     };
     let confirm_struct = LibSqlTest::get(&db, confirm_req).await?;
 
@@ -85,7 +86,7 @@ struct ComplexUpdateTest {
 #[tokio::test]
 async fn add_autoincrement() -> anyhow::Result<()> {
     let db = libsql_client::Client::in_memory().unwrap();
-    db.execute("CREATE TABLE AutoIncrementTest(id INTEGER PRIMARY KEY AUTOINCREMENT, test TEXT NOT NULL);").await?;
+    AutoIncrementTest::create_table(&db).await?;
 
     let test_struct = AutoIncrementTest {
         id: 0,
@@ -95,7 +96,7 @@ async fn add_autoincrement() -> anyhow::Result<()> {
     let result = test_struct.add(&db).await?;
     assert!(result == 1);
     let confirm_req = AutoIncrementTestRequest {
-        id: Some(1),
+        id: Some(Filter::Eq(1)),
         test: None,
     };
 
@@ -109,7 +110,7 @@ async fn add_autoincrement() -> anyhow::Result<()> {
 #[tokio::test]
 async fn update() -> anyhow::Result<()> {
     let db = libsql_client::Client::in_memory().unwrap();
-    db.execute("CREATE TABLE LibSqlTest(id INT PRIMARY KEY, test TEXT NOT NULL);").await?;
+    LibSqlTest::create_table(&db).await?;
     db.execute("INSERT INTO LibSqlTest(id, test) VALUES (1, \"test\");").await?;
     let test_struct = LibSqlTest {
         id: 1,
@@ -120,7 +121,7 @@ async fn update() -> anyhow::Result<()> {
     assert!(result == 1);
     
     let confirm_req = LibSqlTestRequest {
-        id: Some(1), test: None
+        id: Some(Filter::Eq(1)), test: None
     };
     let confirm_struct = LibSqlTest::get(&db, confirm_req).await?;
 
@@ -131,7 +132,7 @@ async fn update() -> anyhow::Result<()> {
 #[tokio::test]
 async fn complex_update() -> anyhow::Result<()> {
     let db = libsql_client::Client::in_memory().unwrap();
-    db.execute("CREATE TABLE ComplexUpdateTest(id1 INT, id2 INT, test TEXT NOT NULL, PRIMARY KEY(id1, id2));").await?;
+    ComplexUpdateTest::create_table(&db).await?;
     db.execute("INSERT INTO ComplexUpdateTest(id1, id2, test) VALUES (1, 1, \"test\");").await?;
     db.execute("INSERT INTO ComplexUpdateTest(id1, id2, test) VALUES (2, 2, \"test\");").await?;
 
@@ -145,10 +146,636 @@ async fn complex_update() -> anyhow::Result<()> {
     assert!(result == 1);
     
     let confirm_req = ComplexUpdateTestRequest {
-        id1: Some(1),id2: Some(1), test: None
+        id1: Some(Filter::Eq(1)),id2: Some(Filter::Eq(1)), test: None
     };
     let confirm_struct = ComplexUpdateTest::get(&db, confirm_req).await?;
 
     assert!(confirm_struct.test == "THE QUICK BROWN FOX");
     Ok(())
 }
+
+#[tokio::test]
+async fn create_table() -> anyhow::Result<()> {
+    assert!(LibSqlTest::create_table_sql() == r#"CREATE TABLE "LibSqlTest" ("id" INTEGER PRIMARY KEY NOT NULL, "test" TEXT NOT NULL);"#);
+    assert!(AutoIncrementTest::create_table_sql() == r#"CREATE TABLE "AutoIncrementTest" ("id" INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, "test" TEXT NOT NULL);"#);
+    assert!(ComplexUpdateTest::create_table_if_not_exists_sql() == r#"CREATE TABLE IF NOT EXISTS "ComplexUpdateTest" ("id1" INTEGER NOT NULL, "id2" INTEGER NOT NULL, "test" TEXT NOT NULL, PRIMARY KEY("id1", "id2"));"#);
+
+    let db = libsql_client::Client::in_memory().unwrap();
+    ComplexUpdateTest::create_table(&db).await?;
+    ComplexUpdateTest::create_table_if_not_exists(&db).await?;
+    assert!(ComplexUpdateTest::create_table(&db).await.is_err());
+
+    Ok(())
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+struct TextKeyTest {
+    #[primary]
+    pub key: String,
+    pub value: i64
+}
+
+#[tokio::test]
+async fn text_key_update() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    TextKeyTest::create_table(&db).await?;
+
+    let key = String::from("o'brien\"; DROP TABLE TextKeyTest; --");
+    TextKeyTest { key: key.clone(), value: 1 }.add(&db).await?;
+
+    let result = TextKeyTest { key: key.clone(), value: 2 }.update(&db).await?;
+    assert!(result == 1);
+
+    let confirm_struct = TextKeyTest::get(&db, TextKeyTestRequest { key: Some(Filter::Eq(key)), value: None }).await?;
+    assert!(confirm_struct.value == 2);
+    Ok(())
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+#[table(name = "users", rename_all = "camelCase")]
+struct LibSqlUser {
+    #[primary]
+    pub user_id: i64,
+    #[column(name = "display")]
+    pub display_name: String,
+    pub created_at: i64
+}
+
+#[tokio::test]
+async fn renamed_table_and_column() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    db.execute("CREATE TABLE users (userId INTEGER PRIMARY KEY, display TEXT NOT NULL, createdAt INTEGER NOT NULL);").await?;
+    assert!(LibSqlUser::create_table_sql() == r#"CREATE TABLE "users" ("userId" INTEGER PRIMARY KEY NOT NULL, "display" TEXT NOT NULL, "createdAt" INTEGER NOT NULL);"#);
+
+    LibSqlUser { user_id: 1, display_name: String::from("first"), created_at: 10 }.add(&db).await?;
+    let result = LibSqlUser { user_id: 1, display_name: String::from("renamed"), created_at: 20 }.update(&db).await?;
+    assert!(result == 1);
+
+    let confirm_req = LibSqlUserRequest {
+        user_id: None, display_name: None, created_at: Some(Filter::Eq(20))
+    };
+    let confirm_struct = LibSqlUser::get(&db, confirm_req).await?;
+    assert!(confirm_struct.user_id == 1);
+    assert!(confirm_struct.display_name == "renamed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn subset_of_wider_table() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    db.execute("CREATE TABLE LibSqlTest(extra TEXT, test TEXT NOT NULL, id INT PRIMARY KEY);").await?;
+    db.execute("INSERT INTO LibSqlTest (extra, test, id) VALUES (\"ignored\", \"test\", 1);").await?;
+    db.execute("ALTER TABLE LibSqlTest ADD COLUMN added_later INTEGER;").await?;
+
+    let test_struct = LibSqlTest::get(&db, LibSqlTestRequest { id: Some(Filter::Eq(1)), test: None }).await?;
+    assert!(test_struct.id == 1);
+    assert!(test_struct.test == "test");
+
+    let test_structs = LibSqlTest::get_many(&db, LibSqlTestRequest::default()).await?;
+    assert!(test_structs.len() == 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn insert_autoincrement() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    AutoIncrementTest::create_table(&db).await?;
+
+    let first_id = AutoIncrementTest { id: 0, test: "first".to_string() }.insert(&db).await?;
+    assert!(first_id == 1);
+
+    let mut second = AutoIncrementTest { id: 0, test: "second".to_string() };
+    let second_id = second.insert_mut(&db).await?;
+    assert!(second_id == 2);
+    assert!(second.id == 2);
+
+    let confirm_struct = AutoIncrementTest::get(&db, AutoIncrementTestRequest { id: Some(Filter::Eq(2)), test: None }).await?;
+    assert!(confirm_struct.test == "second");
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+    db.execute("INSERT INTO LibSqlTest (id, test) VALUES (1, \"test\");").await?;
+
+    let result = LibSqlTest {
+        id: 1,
+        test: String::from("ignored")
+    }.delete(&db).await?;
+    assert!(result == 1);
+
+    let remaining = LibSqlTest::get_many(&db, LibSqlTestRequest::default()).await?;
+    assert!(remaining.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn complex_delete() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    ComplexUpdateTest::create_table(&db).await?;
+    db.execute("INSERT INTO ComplexUpdateTest(id1, id2, test) VALUES (1, 1, \"test\"), (1, 2, \"test\");").await?;
+
+    let result = ComplexUpdateTest {
+        id1: 1,
+        id2: 1,
+        test: String::from("ignored")
+    }.delete(&db).await?;
+    assert!(result == 1);
+
+    let remaining = ComplexUpdateTest::get_many(&db, ComplexUpdateTestRequest::default()).await?;
+    assert!(remaining.len() == 1);
+    assert!(remaining[0].id2 == 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_many() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    ComplexUpdateTest::create_table(&db).await?;
+    db.execute("INSERT INTO ComplexUpdateTest(id1, id2, test) VALUES (1, 1, \"a\"), (1, 2, \"a\"), (2, 1, \"b\");").await?;
+
+    let req = ComplexUpdateTestRequest {
+        id1: None, id2: None, test: Some(Filter::Eq(String::from("a")))
+    };
+    let result = ComplexUpdateTest::delete_many(&db, req).await?;
+    assert!(result == 2);
+
+    let remaining = ComplexUpdateTest::get_many(&db, ComplexUpdateTestRequest::default()).await?;
+    assert!(remaining.len() == 1);
+    assert!(remaining[0].test == "b");
+
+    let result = ComplexUpdateTest::delete_many(&db, ComplexUpdateTestRequest::default()).await?;
+    assert!(result == 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_where_and_update_where() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    ComplexUpdateTest::create_table(&db).await?;
+    db.execute("INSERT INTO ComplexUpdateTest(id1, id2, test) VALUES (1, 1, \"a\"), (1, 2, \"a\"), (2, 1, \"b\");").await?;
+
+    let filter = ComplexUpdateTestRequest {
+        id1: Some(Filter::Eq(1)), id2: None, test: None
+    };
+    let patch = ComplexUpdateTestPatch {
+        id1: None, id2: None, test: Some(String::from("c"))
+    };
+    let result = ComplexUpdateTest::update_where(&db, filter, patch).await?;
+    assert!(result == 2);
+
+    let empty_patch = ComplexUpdateTest::update_where(&db, ComplexUpdateTestRequest::default(), ComplexUpdateTestPatch::default()).await;
+    assert!(empty_patch.is_err());
+
+    let req = ComplexUpdateTestRequest {
+        id1: None, id2: None, test: Some(Filter::Eq(String::from("c")))
+    };
+    let result = ComplexUpdateTest::delete_where(&db, req).await?;
+    assert!(result == 2);
+
+    let remaining = ComplexUpdateTest::get_many(&db, ComplexUpdateTestRequest::default()).await?;
+    assert!(remaining.len() == 1);
+    assert!(remaining[0].test == "b");
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_partial() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    ComplexUpdateTest::create_table(&db).await?;
+    db.execute("INSERT INTO ComplexUpdateTest(id1, id2, test) VALUES (1, 1, \"a\"), (1, 2, \"a\");").await?;
+
+    let patch = ComplexUpdateTestPatch {
+        test: Some(String::from("b")), ..Default::default()
+    };
+    let result = ComplexUpdateTest::update_partial(&db, (1, 2), patch).await?;
+    assert!(result == 1);
+
+    let confirm_req = ComplexUpdateTestRequest {
+        id1: Some(Filter::Eq(1)), id2: Some(Filter::Eq(2)), test: None
+    };
+    let confirm_struct = ComplexUpdateTest::get(&db, confirm_req).await?;
+    assert!(confirm_struct.test == "b");
+
+    let empty_patch = ComplexUpdateTest::update_partial(&db, (1, 1), ComplexUpdateTestPatch::default()).await;
+    assert!(empty_patch.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn filter_operators() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+    db.execute("INSERT INTO LibSqlTest (id, test) VALUES (1, 'apple'), (2, 'banana'), (3, 'apricot'), (4, 'cherry');").await?;
+
+    let greater = LibSqlTest::get_many(&db, LibSqlTestRequest { id: Some(Filter::Gt(2)), test: None }).await?;
+    assert!(greater.iter().map(|row| row.id).collect::<Vec<i64>>() == vec![3, 4]);
+
+    let between = LibSqlTest::get_many(&db, LibSqlTestRequest { id: Some(Filter::Between(2, 3)), test: None }).await?;
+    assert!(between.len() == 2);
+
+    let listed = LibSqlTest::get_many(&db, LibSqlTestRequest { id: Some(Filter::In(vec![1, 4])), test: Some(Filter::Ne(String::from("apple"))) }).await?;
+    assert!(listed.len() == 1);
+    assert!(listed[0].test == "cherry");
+
+    let liked = LibSqlTest::get_many(&db, LibSqlTestRequest { id: None, test: Some(Filter::Like(String::from("ap%"))) }).await?;
+    assert!(liked.len() == 2);
+
+    let globbed = LibSqlTest::get(&db, LibSqlTestRequest { id: Some(Filter::Le(2)), test: Some(Filter::Glob(String::from("b*"))) }).await?;
+    assert!(globbed.id == 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn filter_combinators() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+    db.execute("INSERT INTO LibSqlTest (id, test) VALUES (1, 'apple'), (2, 'banana'), (3, 'apricot'), (4, 'cherry');").await?;
+
+    let apples = LibSqlTestRequest { id: None, test: Some(Filter::Like(String::from("ap%"))) };
+    let last = LibSqlTestRequest { id: Some(Filter::Eq(4)), test: None };
+
+    let either = LibSqlTest::get_many(&db, apples.clone().or(last.clone())).await?;
+    assert!(either.iter().map(|row| row.id).collect::<Vec<i64>>() == vec![1, 3, 4]);
+
+    let neither = LibSqlTest::get(&db, LibSqlTestFilter::Not(Box::new(apples.clone().or(last.clone())))).await?;
+    assert!(neither.id == 2);
+
+    let deleted = LibSqlTest::delete_where(&db, !apples).await?;
+    assert!(deleted == 2);
+    let remaining = LibSqlTest::get_many(&db, LibSqlTestRequest::default()).await?;
+    assert!(remaining.len() == 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_many_with_options() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlUser::create_table(&db).await?;
+    db.execute("INSERT INTO users (userId, display, createdAt) VALUES (1, 'a', 30), (2, 'b', 10), (3, 'c', 20), (4, 'd', 40);").await?;
+
+    let newest = LibSqlUser::get_many_with(&db, LibSqlUserRequest::default(), QueryOptions::new().desc(LibSqlUserField::CreatedAt).limit(2)).await?;
+    assert!(newest.iter().map(|user| user.user_id).collect::<Vec<i64>>() == vec![4, 1]);
+
+    let page = LibSqlUser::get_many_with(&db, LibSqlUserRequest::default(), QueryOptions::new().asc(LibSqlUserField::DisplayName).limit(2).offset(2)).await?;
+    assert!(page.iter().map(|user| user.display_name.as_str()).collect::<Vec<&str>>() == vec!["c", "d"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn page_after() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlUser::create_table(&db).await?;
+    db.execute("INSERT INTO users (userId, display, createdAt) VALUES (1, 'a', 30), (2, 'b', 10), (3, 'c', 30), (4, 'd', 20);").await?;
+
+    let first = LibSqlUser::page_after(&db, LibSqlUserRequest::default(), None, 3).await?;
+    assert!(first.rows.iter().map(|user| user.user_id).collect::<Vec<i64>>() == vec![1, 2, 3]);
+    let second = LibSqlUser::page_after(&db, LibSqlUserRequest::default(), first.next_cursor.as_ref(), 3).await?;
+    assert!(second.rows.iter().map(|user| user.user_id).collect::<Vec<i64>>() == vec![4]);
+    assert!(second.next_cursor.is_none());
+
+    let by_date = LibSqlUser::page_after_by(&db, LibSqlUserRequest::default(), &[LibSqlUserField::CreatedAt], None, 2).await?;
+    assert!(by_date.rows.iter().map(|user| user.user_id).collect::<Vec<i64>>() == vec![2, 4]);
+    let by_date = LibSqlUser::page_after_by(&db, LibSqlUserRequest::default(), &[LibSqlUserField::CreatedAt], by_date.next_cursor.as_ref(), 2).await?;
+    assert!(by_date.rows.iter().map(|user| user.user_id).collect::<Vec<i64>>() == vec![1, 3]);
+    assert!(by_date.next_cursor.is_none());
+
+    Ok(())
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+struct TextOnlyTest {
+    #[primary]
+    pub name: String,
+}
+
+#[tokio::test]
+async fn count_exists_and_aggregates() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlUser::create_table(&db).await?;
+    db.execute("INSERT INTO users (userId, display, createdAt) VALUES (1, 'a', 30), (2, 'b', 10), (3, 'c', 30), (4, 'd', 20);").await?;
+
+    assert!(LibSqlUser::count(&db, LibSqlUserRequest::default()).await? == 4);
+    assert!(LibSqlUser::count(&db, LibSqlUserRequest { created_at: Some(Filter::Eq(30)), ..Default::default() }).await? == 2);
+    assert!(LibSqlUser::exists(&db, LibSqlUserRequest { display_name: Some(Filter::Eq(String::from("d"))), ..Default::default() }).await?);
+    assert!(!LibSqlUser::exists(&db, LibSqlUserRequest { user_id: Some(Filter::Gt(4)), ..Default::default() }).await?);
+
+    assert!(LibSqlUser::sum_created_at(&db, LibSqlUserRequest::default()).await? == Some(90));
+    assert!(LibSqlUser::min_created_at(&db, LibSqlUserRequest::default()).await? == Some(10));
+    assert!(LibSqlUser::max_user_id(&db, LibSqlUserRequest { created_at: Some(Filter::Eq(30)), ..Default::default() }).await? == Some(3));
+    assert!(LibSqlUser::avg_created_at(&db, LibSqlUserRequest::default()).await? == Some(22.5));
+    assert!(LibSqlUser::sum_created_at(&db, LibSqlUserRequest { user_id: Some(Filter::Gt(4)), ..Default::default() }).await?.is_none());
+
+    TextOnlyTest::create_table(&db).await?;
+    assert!(TextOnlyTest::count(&db, TextOnlyTestRequest::default()).await? == 0);
+    assert!(!TextOnlyTest::exists(&db, TextOnlyTestRequest::default()).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn stream() -> anyhow::Result<()> {
+    use futures::StreamExt;
+
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+    db.execute("INSERT INTO LibSqlTest (id, test) VALUES (5, 'e'), (2, 'b'), (4, 'd'), (1, 'a'), (3, 'c');").await?;
+
+    let rows: Vec<sqlite_helper::Result<LibSqlTest>> = LibSqlTest::stream(&db, LibSqlTestRequest::default(), 2).collect().await;
+    let ids = rows.into_iter().map(|row| row.map(|row| row.id)).collect::<sqlite_helper::Result<Vec<i64>>>()?;
+    assert!(ids == vec![1, 2, 3, 4, 5]);
+
+    let filtered: Vec<sqlite_helper::Result<LibSqlTest>> = LibSqlTest::stream(&db, LibSqlTestRequest { id: Some(Filter::Ge(4)), test: None }, 10).collect().await;
+    assert!(filtered.len() == 2);
+
+    let empty: Vec<sqlite_helper::Result<LibSqlTest>> = LibSqlTest::stream(&db, LibSqlTestRequest { id: Some(Filter::Gt(5)), test: None }, 10).collect().await;
+    assert!(empty.is_empty());
+
+    let failed: Vec<sqlite_helper::Result<LibSqlTest>> = LibSqlTest::stream(&db, LibSqlTestRequest::default(), 0).collect().await;
+    assert!(failed.len() == 1);
+    assert!(failed[0].is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_not_found_and_not_unique() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+    db.execute("INSERT INTO LibSqlTest (id, test) VALUES (3, 'same'), (1, 'same'), (2, 'other');").await?;
+
+    let missing = LibSqlTest::get(&db, LibSqlTestRequest { id: Some(Filter::Eq(4)), test: None }).await.err().unwrap();
+    assert!(matches!(missing, sqlite_helper::Error::NotFound));
+    let same = LibSqlTestRequest { id: None, test: Some(Filter::Eq(String::from("same"))) };
+    let duplicate = LibSqlTest::get(&db, same.clone()).await.err().unwrap();
+    assert!(matches!(duplicate, sqlite_helper::Error::NotUnique));
+
+    assert!(LibSqlTest::get_optional(&db, LibSqlTestRequest { id: Some(Filter::Eq(4)), test: None }).await?.is_none());
+    assert!(LibSqlTest::get_optional(&db, LibSqlTestRequest { id: Some(Filter::Eq(2)), test: None }).await?.map(|row| row.id) == Some(2));
+    assert!(LibSqlTest::get_first(&db, same).await?.id == 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn constraint_and_conversion_errors() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+    LibSqlTest { id: 1, test: String::from("a") }.add(&db).await?;
+
+    // libsql_client only hands back the message, which names an INTEGER PRIMARY KEY violation UNIQUE.
+    match (LibSqlTest { id: 1, test: String::from("b") }).add(&db).await.err().unwrap() {
+        sqlite_helper::Error::Constraint { kind, table, columns, .. } => {
+            assert!(kind == sqlite_helper::ConstraintKind::Unique);
+            assert!(table.as_deref() == Some("LibSqlTest"));
+            assert!(columns == vec!["id"]);
+        },
+        err => panic!("expected a constraint error, got {:?}", err),
+    }
+
+    db.execute("CREATE TABLE users (userId INTEGER PRIMARY KEY, display TEXT NOT NULL, createdAt INTEGER NOT NULL, email TEXT NOT NULL);").await?;
+    match (LibSqlUser { user_id: 1, display_name: String::from("first"), created_at: 10 }).add(&db).await.err().unwrap() {
+        sqlite_helper::Error::Constraint { kind, columns, .. } => {
+            assert!(kind == sqlite_helper::ConstraintKind::NotNull);
+            assert!(columns == vec!["email"]);
+        },
+        err => panic!("expected a constraint error, got {:?}", err),
+    }
+
+    db.execute("INSERT INTO LibSqlTest (id, test) VALUES (2, x'00');").await?;
+    assert!(matches!(LibSqlTest::get_many(&db, LibSqlTestRequest::default()).await.err().unwrap(), sqlite_helper::Error::Conversion(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+
+    let tx = db.transaction().await?;
+    LibSqlTest { id: 1, test: String::from("a") }.add(&tx).await?;
+    assert!(LibSqlTest::count(&tx, LibSqlTestRequest::default()).await? == 1);
+    tx.rollback().await?;
+    assert!(LibSqlTest::count(&db, LibSqlTestRequest::default()).await? == 0);
+
+    let added = sqlite_helper::transaction(&db, |tx| Box::pin(async move {
+        LibSqlTest { id: 1, test: String::from("a") }.add(tx).await?;
+        LibSqlTest { id: 2, test: String::from("b") }.add(tx).await?;
+        Ok::<_, sqlite_helper::Error>(2)
+    })).await?;
+    assert!(added == 2);
+    assert!(LibSqlTest::count(&db, LibSqlTestRequest::default()).await? == 2);
+
+    // The duplicate id fails the second add, which rolls back the first one.
+    let failed = sqlite_helper::transaction(&db, |tx| Box::pin(async move {
+        LibSqlTest { id: 3, test: String::from("c") }.add(tx).await?;
+        LibSqlTest { id: 1, test: String::from("a") }.add(tx).await?;
+        Ok::<_, sqlite_helper::Error>(())
+    })).await;
+    assert!(matches!(failed, Err(sqlite_helper::Error::Constraint { .. })));
+    assert!(LibSqlTest::count(&db, LibSqlTestRequest::default()).await? == 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_many() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+
+    // 2 columns a row, more rows than fit under the parameter limit in one statement.
+    let rows: Vec<LibSqlTest> = (1..=1200).map(|id| LibSqlTest { id, test: id.to_string() }).collect();
+    assert!(LibSqlTest::add_many(&db, &rows).await? == 1200);
+    assert!(LibSqlTest::count(&db, LibSqlTestRequest::default()).await? == 1200);
+    assert!(LibSqlTest::get(&db, LibSqlTestRequest { id: Some(Filter::Eq(1100)), test: None }).await?.test == "1100");
+    assert!(LibSqlTest::add_many(&db, &[]).await? == 0);
+
+    // The duplicate in the last chunk undoes the rows added before it.
+    let rows: Vec<LibSqlTest> = (1201..=1800).chain(1..=1).map(|id| LibSqlTest { id, test: id.to_string() }).collect();
+    assert!(matches!(LibSqlTest::add_many(&db, &rows).await, Err(sqlite_helper::Error::Constraint { .. })));
+    assert!(LibSqlTest::count(&db, LibSqlTestRequest::default()).await? == 1200);
+
+    let tx = db.transaction().await?;
+    LibSqlTest::add_many(&tx, &[LibSqlTest { id: 2000, test: String::from("a") }]).await?;
+    tx.rollback().await?;
+    assert!(LibSqlTest::count(&db, LibSqlTestRequest::default()).await? == 1200);
+
+    AutoIncrementTest::create_table(&db).await?;
+    let rows = vec![AutoIncrementTest { id: 0, test: String::from("a") }, AutoIncrementTest { id: 0, test: String::from("b") }];
+    assert!(AutoIncrementTest::add_many(&db, &rows).await? == 2);
+    let ids: Vec<usize> = AutoIncrementTest::get_many(&db, AutoIncrementTestRequest::default()).await?.into_iter().map(|row| row.id).collect();
+    assert!(ids == vec![1, 2]);
+
+    Ok(())
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+#[table(name = "accounts")]
+struct LibSqlAccount {
+    #[primary(autoincrement)]
+    pub id: i64,
+    #[unique]
+    pub email: String,
+    pub name: String
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+struct LibSqlMember {
+    #[primary]
+    pub id: i64,
+    #[unique]
+    pub email: String,
+    pub name: String
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+struct LibSqlOptionalId {
+    #[primary(autoincrement)]
+    pub id: Option<i64>,
+    pub name: String
+}
+
+#[tokio::test]
+async fn upsert_and_insert_or() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlTest::create_table(&db).await?;
+
+    assert!(LibSqlTest { id: 1, test: String::from("a") }.upsert(&db).await? == 1);
+    assert!(LibSqlTest { id: 1, test: String::from("b") }.upsert(&db).await? == 1);
+    assert!(LibSqlTest::get(&db, LibSqlTestRequest::default()).await?.test == "b");
+
+    assert!(LibSqlTest { id: 1, test: String::from("c") }.insert_or_ignore(&db).await? == 0);
+    assert!(LibSqlTest::get(&db, LibSqlTestRequest::default()).await?.test == "b");
+    assert!(LibSqlTest { id: 1, test: String::from("c") }.insert_or_replace(&db).await? == 1);
+    assert!(LibSqlTest::get(&db, LibSqlTestRequest::default()).await?.test == "c");
+
+    LibSqlAccount::create_table(&db).await?;
+    LibSqlAccount { id: 0, email: String::from("a@example.com"), name: String::from("first") }.upsert(&db).await?;
+    LibSqlAccount { id: 0, email: String::from("a@example.com"), name: String::from("second") }.upsert(&db).await?;
+    let account = LibSqlAccount::get(&db, LibSqlAccountRequest::default()).await?;
+    assert!(account.id == 1 && account.name == "second");
+
+    // Matching on the unique email leaves the existing row's primary key alone.
+    LibSqlMember::create_table(&db).await?;
+    LibSqlMember { id: 1, email: String::from("a@example.com"), name: String::from("first") }.upsert(&db).await?;
+    assert!(LibSqlMember { id: 99, email: String::from("a@example.com"), name: String::from("second") }.upsert(&db).await? == 1);
+    let member = LibSqlMember::get(&db, LibSqlMemberRequest::default()).await?;
+    assert!(member.id == 1 && member.name == "second");
+
+    // A plain autoincrement id is a placeholder, so every upsert adds a row with an id SQLite picks.
+    AutoIncrementTest::create_table(&db).await?;
+    AutoIncrementTest { id: 0, test: String::from("a") }.upsert(&db).await?;
+    AutoIncrementTest { id: 0, test: String::from("b") }.upsert(&db).await?;
+    let ids: Vec<usize> = AutoIncrementTest::get_many(&db, AutoIncrementTestRequest::default()).await?.into_iter().map(|row| row.id).collect();
+    assert!(ids == vec![1, 2]);
+
+    // An Option id is the conflict target when it is Some, and left to SQLite when it is None.
+    LibSqlOptionalId::create_table(&db).await?;
+    LibSqlOptionalId { id: None, name: String::from("a") }.upsert(&db).await?;
+    assert!(LibSqlOptionalId { id: Some(1), name: String::from("b") }.upsert(&db).await? == 1);
+    let row = LibSqlOptionalId::get(&db, LibSqlOptionalIdRequest::default()).await?;
+    assert!(row.id == Some(1) && row.name == "b");
+
+    assert!(LibSqlOptionalId { id: Some(1), name: String::from("c") }.insert_or_ignore(&db).await? == 0);
+    assert!(LibSqlOptionalId { id: Some(1), name: String::from("c") }.insert_or_replace(&db).await? == 1);
+    LibSqlOptionalId { id: None, name: String::from("d") }.insert_or_replace(&db).await?;
+    let rows: Vec<(Option<i64>, String)> = LibSqlOptionalId::get_many(&db, LibSqlOptionalIdRequest::default()).await?.into_iter().map(|row| (row.id, row.name)).collect();
+    assert!(rows == vec![(Some(1), String::from("c")), (Some(2), String::from("d"))]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returning() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlAccount::create_table(&db).await?;
+
+    let account = LibSqlAccount { id: 0, email: String::from("a@example.com"), name: String::from("first") }.add_returning(&db).await?;
+    assert!(account.id == 1 && account.name == "first");
+    let accounts = LibSqlAccount::add_many_returning(&db, &[
+        LibSqlAccount { id: 0, email: String::from("b@example.com"), name: String::from("second") },
+        LibSqlAccount { id: 0, email: String::from("c@example.com"), name: String::from("third") },
+    ]).await?;
+    let mut ids: Vec<i64> = accounts.iter().map(|account| account.id).collect();
+    ids.sort();
+    assert!(ids == vec![2, 3]);
+
+    let updated = LibSqlAccount { id: 1, email: String::from("a@example.com"), name: String::from("renamed") }.update_returning(&db).await?;
+    assert!(updated.name == "renamed");
+    let missing = LibSqlAccount { id: 10, email: String::from("z@example.com"), name: String::from("missing") };
+    assert!(matches!(missing.update_returning(&db).await, Err(sqlite_helper::Error::NotFound)));
+
+    let patch = LibSqlAccountPatch { name: Some(String::from("bulk")), ..Default::default() };
+    let updated = LibSqlAccount::update_where_returning(&db, LibSqlAccountRequest { id: Some(Filter::Gt(1)), ..Default::default() }, patch).await?;
+    assert!(updated.len() == 2 && updated.iter().all(|account| account.name == "bulk"));
+
+    let deleted = LibSqlAccount { id: 1, email: String::new(), name: String::new() }.delete_returning(&db).await?;
+    assert!(deleted.email == "a@example.com" && deleted.name == "renamed");
+    assert!(matches!(deleted.delete_returning(&db).await, Err(sqlite_helper::Error::NotFound)));
+    assert!(LibSqlAccount::delete_where_returning(&db, LibSqlAccountRequest::default()).await?.len() == 2);
+    assert!(LibSqlAccount::count(&db, LibSqlAccountRequest::default()).await? == 0);
+
+    // Renamed columns come back under their field names.
+    LibSqlUser::create_table(&db).await?;
+    let user = LibSqlUser { user_id: 1, display_name: String::from("first"), created_at: 10 }.add_returning(&db).await?;
+    assert!(user.display_name == "first" && user.created_at == 10);
+
+    Ok(())
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+struct LibSqlLink {
+    #[primary]
+    pub a: i64,
+    #[primary]
+    pub b: i64
+}
+
+#[tokio::test]
+async fn key_only_struct() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlLink::create_table(&db).await?;
+
+    // No `update` is generated, every column is part of the key.
+    LibSqlLink { a: 1, b: 2 }.add(&db).await?;
+    LibSqlLink { a: 1, b: 3 }.add(&db).await?;
+    assert!(LibSqlLink { a: 1, b: 2 }.upsert(&db).await? == 0);
+    assert!(LibSqlLink::update_partial(&db, (1, 3), LibSqlLinkPatch { b: Some(4), ..Default::default() }).await? == 1);
+    assert!(LibSqlLink::get(&db, LibSqlLinkRequest { b: Some(Filter::Eq(4)), ..Default::default() }).await?.a == 1);
+
+    assert!(LibSqlLink { a: 1, b: 2 }.delete(&db).await? == 1);
+    assert!(LibSqlLink::count(&db, LibSqlLinkRequest::default()).await? == 1);
+
+    Ok(())
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+#[table(unique(owner, slug))]
+struct LibSqlProject {
+    #[primary(autoincrement)]
+    pub id: i64,
+    pub owner: String,
+    pub slug: String,
+    pub title: String
+}
+
+#[tokio::test]
+async fn unique_key_upsert() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlProject::create_table(&db).await?;
+
+    LibSqlProject { id: 0, owner: String::from("a"), slug: String::from("site"), title: String::from("first") }.add(&db).await?;
+    LibSqlProject { id: 0, owner: String::from("b"), slug: String::from("site"), title: String::from("other") }.add(&db).await?;
+    LibSqlProject { id: 0, owner: String::from("a"), slug: String::from("site"), title: String::from("second") }.upsert(&db).await?;
+    let project = LibSqlProject::get(&db, LibSqlProjectRequest { owner: Some(Filter::Eq(String::from("a"))), ..Default::default() }).await?;
+    assert!(project.id == 1 && project.title == "second");
+    assert!(LibSqlProject::count(&db, LibSqlProjectRequest::default()).await? == 2);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use database_macros::Queryable;
 use anyhow::Context;
+use sqlite_helper::Filter;
 
 #[derive(Queryable)]
 struct TestModel {
@@ -9,7 +10,7 @@ struct TestModel {
 }
 
 fn initialize_table(conn: &rusqlite::Connection) -> anyhow::Result<()> {
-    conn.execute("CREATE TABLE TestModel (id INT PRIMARY KEY, comments TEXT, test_val TEXT NOT NULL);", [])?;
+    TestModel::create_table(conn)?;
     Ok(())
 }
 
@@ -23,7 +24,7 @@ fn get() -> anyhow::Result<()> {
     conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'hello', 'test_val'), (2, NULL, 'test2');", [])?;
 
     let mut single_request = TestModelRequest::default();
-    single_request.id = Some(1);
+    single_request.id = Some(Filter::Eq(1));
 
     let test_model = TestModel::get(&conn, single_request)?;
     assert_eq!(test_model.id, 1);
@@ -114,3 +115,20 @@ fn update() -> anyhow::Result<()> {
 
     return Ok(());
 }
+
+#[test]
+fn transaction_rollback() -> anyhow::Result<()> {
+    let manager = r2d2_sqlite::SqliteConnectionManager::memory();
+    let pool = r2d2::Pool::new(manager)?;
+    let mut conn: r2d2::PooledConnection<r2d2_sqlite::SqliteConnectionManager> = pool.get()?;
+    initialize_table(&conn)?;
+
+    let tx = conn.transaction()?;
+    TestModel { id: 1, comments: None, test_val: String::from("a") }.add(&tx)?;
+    assert_eq!(TestModel::get_many(&tx, TestModelRequest::default())?.len(), 1);
+    tx.rollback()?;
+
+    assert!(TestModel::get_many(&conn, TestModelRequest::default())?.is_empty());
+
+    return Ok(());
+}
@@ -0,0 +1,99 @@
+use database_macros::{Queryable, LibSqlQueryable};
+use serde::{Serialize, Deserialize};
+use sqlite_helper::Filter;
+
+#[derive(Queryable, Debug)]
+struct Order {
+    #[primary]
+    pub index: i64,
+    pub group: String,
+    pub order: Option<i64>,
+    pub r#where: String
+}
+
+#[derive(LibSqlQueryable, Serialize, Deserialize)]
+#[table(name = "Select")]
+struct LibSqlOrder {
+    #[primary]
+    pub index: i64,
+    #[column(name = "group by")]
+    pub group: String,
+    pub order: Option<i64>,
+    pub r#where: String
+}
+
+#[derive(Queryable, Debug)]
+#[table(name = "weird \"table\"", rename_all = "kebab-case")]
+struct QuotedNames {
+    #[primary]
+    pub row_id: i64,
+    pub display_name: String
+}
+
+#[test]
+fn quoted_create_table_sql() {
+    assert_eq!(Order::create_table_sql(), r#"CREATE TABLE "Order" ("index" INTEGER PRIMARY KEY NOT NULL, "group" TEXT NOT NULL, "order" INTEGER, "where" TEXT NOT NULL);"#);
+    assert_eq!(QuotedNames::create_table_sql(), r#"CREATE TABLE "weird ""table""" ("row-id" INTEGER PRIMARY KEY NOT NULL, "display-name" TEXT NOT NULL);"#);
+}
+
+#[test]
+fn rusqlite_reserved_words() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    Order::create_table(&conn)?;
+
+    Order { index: 1, group: String::from("a"), order: None, r#where: String::from("here") }.add(&conn)?;
+    Order { index: 2, group: String::from("b"), order: Some(2), r#where: String::from("there") }.add(&conn)?;
+
+    let updated = Order { index: 1, group: String::from("c"), order: Some(1), r#where: String::from("here") }.update(&conn)?;
+    assert_eq!(updated, 1);
+
+    let order = Order::get(&conn, OrderRequest { group: Some(Filter::Eq(String::from("c"))), ..Default::default() })?;
+    assert_eq!(order.index, 1);
+    assert_eq!(order.order, Some(1));
+
+    let orders = Order::get_many(&conn, OrderRequest { r#where: Some(Filter::Eq(String::from("there"))), ..Default::default() })?;
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].index, 2);
+
+    assert_eq!(order.delete(&conn)?, 1);
+    assert_eq!(Order::get_many(&conn, OrderRequest::default())?.len(), 1);
+
+    return Ok(());
+}
+
+#[test]
+fn rusqlite_quotes_in_names() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    QuotedNames::create_table(&conn)?;
+
+    QuotedNames { row_id: 1, display_name: String::from("first") }.add(&conn)?;
+    QuotedNames { row_id: 1, display_name: String::from("second") }.update(&conn)?;
+
+    let row = QuotedNames::get(&conn, QuotedNamesRequest { row_id: Some(Filter::Eq(1)), ..Default::default() })?;
+    assert_eq!(row.display_name, "second");
+    assert_eq!(row.delete(&conn)?, 1);
+
+    return Ok(());
+}
+
+#[tokio::test]
+async fn libsql_reserved_words() -> anyhow::Result<()> {
+    let db = libsql_client::Client::in_memory().unwrap();
+    LibSqlOrder::create_table(&db).await?;
+
+    LibSqlOrder { index: 1, group: String::from("a"), order: None, r#where: String::from("here") }.add(&db).await?;
+    LibSqlOrder { index: 2, group: String::from("b"), order: Some(2), r#where: String::from("there") }.add(&db).await?;
+
+    let updated = LibSqlOrder { index: 1, group: String::from("c"), order: Some(1), r#where: String::from("here") }.update(&db).await?;
+    assert!(updated == 1);
+
+    let order = LibSqlOrder::get(&db, LibSqlOrderRequest { group: Some(Filter::Eq(String::from("c"))), ..Default::default() }).await?;
+    assert!(order.index == 1);
+    assert!(order.order == Some(1));
+
+    let orders = LibSqlOrder::get_many(&db, LibSqlOrderRequest { r#where: Some(Filter::Eq(String::from("there"))), ..Default::default() }).await?;
+    assert!(orders.len() == 1);
+    assert!(orders[0].index == 2);
+
+    Ok(())
+}
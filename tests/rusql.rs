@@ -1,5 +1,6 @@
 use database_macros::Queryable;
 use anyhow::Context;
+use sqlite_helper::{Filter, QueryOptions, Direction, Cursor};
 
 #[derive(Queryable, Debug)]
 struct TestModel {
@@ -9,7 +10,7 @@ struct TestModel {
 }
 
 fn initialize_table(conn: &rusqlite::Connection) -> anyhow::Result<()> {
-    conn.execute("CREATE TABLE TestModel (id INT PRIMARY KEY, comments TEXT, test_val TEXT NOT NULL);", [])?;
+    TestModel::create_table(conn)?;
     Ok(())
 }
 
@@ -20,7 +21,7 @@ fn get() -> anyhow::Result<()> {
     conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'hello', 'test_val'), (2, NULL, 'test2');", [])?;
 
     let mut single_request = TestModelRequest::default();
-    single_request.id = Some(1);
+    single_request.id = Some(Filter::Eq(1));
 
     let test_model = TestModel::get(&conn, single_request)?;
     assert_eq!(test_model.id, 1);
@@ -124,3 +125,852 @@ fn delete() -> anyhow::Result<()> {
     assert_eq!(exists.expect_err("Row should have been deleted"), rusqlite::Error::QueryReturnedNoRows);
     return Ok(());
 }
+
+#[derive(Queryable, Debug)]
+struct CompositeModel {
+    #[primary]
+    pub id1: i64,
+    #[primary]
+    pub id2: i64,
+    pub blob: Vec<u8>,
+    pub score: Option<f64>
+}
+
+#[test]
+fn create_table_sql() -> anyhow::Result<()> {
+    assert_eq!(TestModel::create_table_sql(), r#"CREATE TABLE "TestModel" ("id" INTEGER PRIMARY KEY NOT NULL, "comments" TEXT, "test_val" TEXT NOT NULL);"#);
+    assert_eq!(CompositeModel::create_table_sql(), r#"CREATE TABLE "CompositeModel" ("id1" INTEGER NOT NULL, "id2" INTEGER NOT NULL, "blob" BLOB NOT NULL, "score" REAL, PRIMARY KEY("id1", "id2"));"#);
+    assert_eq!(CompositeModel::create_table_if_not_exists_sql(), r#"CREATE TABLE IF NOT EXISTS "CompositeModel" ("id1" INTEGER NOT NULL, "id2" INTEGER NOT NULL, "blob" BLOB NOT NULL, "score" REAL, PRIMARY KEY("id1", "id2"));"#);
+
+    return Ok(());
+}
+
+#[test]
+fn create_table() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    CompositeModel::create_table(&conn)?;
+    CompositeModel::create_table_if_not_exists(&conn)?;
+    assert!(CompositeModel::create_table(&conn).is_err());
+
+    conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 1, x'00', NULL);", [])?;
+    let duplicate = conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 1, x'01', NULL);", []);
+    assert!(duplicate.is_err());
+    let missing_blob = conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 2, NULL, NULL);", []);
+    assert!(missing_blob.is_err());
+
+    return Ok(());
+}
+
+#[test]
+fn composite_update() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    CompositeModel::create_table(&conn)?;
+    conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 1, x'00', NULL), (1, 2, x'00', NULL);", [])?;
+
+    let result = CompositeModel {
+        id1: 1,
+        id2: 2,
+        blob: vec![1, 2, 3],
+        score: Some(0.5)
+    }.update(&conn)?;
+    assert_eq!(result, 1);
+
+    let untouched = CompositeModel::get(&conn, CompositeModelRequest { id1: Some(Filter::Eq(1)), id2: Some(Filter::Eq(1)), ..Default::default() })?;
+    assert_eq!(untouched.blob, vec![0]);
+    assert_eq!(untouched.score, None);
+    let updated = CompositeModel::get(&conn, CompositeModelRequest { id1: Some(Filter::Eq(1)), id2: Some(Filter::Eq(2)), ..Default::default() })?;
+    assert_eq!(updated.blob, vec![1, 2, 3]);
+    assert_eq!(updated.score, Some(0.5));
+
+    return Ok(());
+}
+
+#[test]
+fn composite_delete() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    CompositeModel::create_table(&conn)?;
+    conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 1, x'00', NULL), (1, 2, x'00', NULL);", [])?;
+
+    let result = CompositeModel {
+        id1: 1,
+        id2: 1,
+        blob: vec![],
+        score: None
+    }.delete(&conn)?;
+    assert_eq!(result, 1);
+
+    let remaining = CompositeModel::get_many(&conn, CompositeModelRequest::default())?;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id2, 2);
+
+    return Ok(());
+}
+
+#[derive(Queryable, Debug)]
+struct TextKeyModel {
+    #[primary]
+    pub key: String,
+    pub value: i64
+}
+
+#[test]
+fn text_key_update_and_delete() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    TextKeyModel::create_table(&conn)?;
+
+    let key = String::from("o'brien\"; DROP TABLE TextKeyModel; --");
+    TextKeyModel { key: key.clone(), value: 1 }.add(&conn)?;
+    TextKeyModel { key: String::from("other"), value: 1 }.add(&conn)?;
+
+    let updated = TextKeyModel { key: key.clone(), value: 2 }.update(&conn)?;
+    assert_eq!(updated, 1);
+    let model = TextKeyModel::get(&conn, TextKeyModelRequest { key: Some(Filter::Eq(key.clone())), value: None })?;
+    assert_eq!(model.value, 2);
+
+    let deleted = TextKeyModel { key: key.clone(), value: 2 }.delete(&conn)?;
+    assert_eq!(deleted, 1);
+    let remaining = TextKeyModel::get_many(&conn, TextKeyModelRequest::default())?;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].key, "other");
+
+    return Ok(());
+}
+
+#[derive(Queryable, Debug)]
+#[table(name = "users")]
+struct User {
+    #[primary]
+    pub id: i64,
+    pub name: String,
+    #[column(name = "createdAt")]
+    pub created_at: i64
+}
+
+#[test]
+fn renamed_table_and_column() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, createdAt INTEGER NOT NULL);", [])?;
+    assert_eq!(User::create_table_sql(), r#"CREATE TABLE "users" ("id" INTEGER PRIMARY KEY NOT NULL, "name" TEXT NOT NULL, "createdAt" INTEGER NOT NULL);"#);
+
+    User { id: 1, name: String::from("first"), created_at: 10 }.add(&conn)?;
+    User { id: 1, name: String::from("renamed"), created_at: 20 }.update(&conn)?;
+
+    let user = User::get(&conn, UserRequest { created_at: Some(Filter::Eq(20)), ..Default::default() })?;
+    assert_eq!(user.name, "renamed");
+
+    let created_at: i64 = conn.query_row("SELECT createdAt FROM users WHERE id = 1", [], |row| row.get(0))?;
+    assert_eq!(created_at, 20);
+
+    assert_eq!(user.delete(&conn)?, 1);
+
+    return Ok(());
+}
+
+#[derive(Queryable)]
+#[allow(dead_code)]
+#[table(rename_all = "snake_case")]
+struct AuditLogEntry {
+    #[primary]
+    pub entry_id: i64,
+    pub message: String
+}
+
+#[derive(Queryable, Debug)]
+#[table(name = "Events", rename_all = "camelCase")]
+struct EventRow {
+    #[primary]
+    pub event_id: i64,
+    pub occurred_at: i64
+}
+
+#[test]
+fn rename_all() -> anyhow::Result<()> {
+    assert_eq!(AuditLogEntry::create_table_sql(), r#"CREATE TABLE "audit_log_entry" ("entry_id" INTEGER PRIMARY KEY NOT NULL, "message" TEXT NOT NULL);"#);
+    assert_eq!(EventRow::create_table_sql(), r#"CREATE TABLE "Events" ("eventId" INTEGER PRIMARY KEY NOT NULL, "occurredAt" INTEGER NOT NULL);"#);
+
+    let conn = rusqlite::Connection::open_in_memory()?;
+    EventRow::create_table(&conn)?;
+    EventRow { event_id: 1, occurred_at: 5 }.add(&conn)?;
+    let event = EventRow::get(&conn, EventRowRequest { occurred_at: Some(Filter::Eq(5)), ..Default::default() })?;
+    assert_eq!(event.event_id, 1);
+
+    return Ok(());
+}
+
+#[test]
+fn subset_of_wider_table() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    conn.execute("CREATE TABLE TestModel (extra TEXT, test_val TEXT NOT NULL, comments TEXT, id INT PRIMARY KEY);", [])?;
+    conn.execute("INSERT INTO TestModel (extra, test_val, comments, id) VALUES ('ignored', 'test_val', 'hello', 1);", [])?;
+    conn.execute("ALTER TABLE TestModel ADD COLUMN added_later INTEGER;", [])?;
+
+    let test_model = TestModel::get(&conn, TestModelRequest { id: Some(Filter::Eq(1)), ..Default::default() })?;
+    assert_eq!(test_model.id, 1);
+    assert_eq!(test_model.comments, Some("hello".to_string()));
+    assert_eq!(test_model.test_val, "test_val");
+
+    let test_models = TestModel::get_many(&conn, TestModelRequest::default())?;
+    assert_eq!(test_models.len(), 1);
+    assert_eq!(test_models[0].test_val, "test_val");
+
+    return Ok(());
+}
+
+#[derive(Queryable, Debug)]
+struct AutoIncrementModel {
+    #[primary(autoincrement)]
+    pub id: usize,
+    pub name: String
+}
+
+#[derive(Queryable, Debug)]
+struct OptionalIdModel {
+    #[primary(autoincrement)]
+    pub id: Option<i64>,
+    pub name: String
+}
+
+#[test]
+fn insert_autoincrement() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    AutoIncrementModel::create_table(&conn)?;
+
+    let first_id = AutoIncrementModel { id: 0, name: String::from("first") }.insert(&conn)?;
+    assert_eq!(first_id, 1);
+
+    let mut second = AutoIncrementModel { id: 0, name: String::from("second") };
+    let second_id = second.insert_mut(&conn)?;
+    assert_eq!(second_id, 2);
+    assert_eq!(second.id, 2);
+
+    let confirm = AutoIncrementModel::get(&conn, AutoIncrementModelRequest { id: Some(Filter::Eq(2)), ..Default::default() })?;
+    assert_eq!(confirm.name, "second");
+
+    OptionalIdModel::create_table(&conn)?;
+    let mut optional = OptionalIdModel { id: None, name: String::from("optional") };
+    optional.insert_mut(&conn)?;
+    assert_eq!(optional.id, Some(1));
+
+    return Ok(());
+}
+
+#[test]
+fn delete_where() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'a', 'keep'), (2, 'a', 'drop'), (3, NULL, 'drop');", [])?;
+
+    let deleted = TestModel::delete_where(&conn, TestModelRequest { test_val: Some(Filter::Eq(String::from("drop"))), ..Default::default() })?;
+    assert_eq!(deleted, 2);
+
+    let remaining = TestModel::get_many(&conn, TestModelRequest::default())?;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, 1);
+
+    return Ok(());
+}
+
+#[test]
+fn update_where() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'a', 'old'), (2, 'a', 'old'), (3, 'b', 'old');", [])?;
+
+    let filter = TestModelRequest { comments: Some(Filter::Eq(String::from("a"))), ..Default::default() };
+    let patch = TestModelPatch { test_val: Some(String::from("new")), comments: Some(None), ..Default::default() };
+    let updated = TestModel::update_where(&conn, filter, patch)?;
+    assert_eq!(updated, 2);
+
+    let changed = TestModel::get_many(&conn, TestModelRequest { test_val: Some(Filter::Eq(String::from("new"))), ..Default::default() })?;
+    assert_eq!(changed.len(), 2);
+    assert!(changed.iter().all(|model| model.comments.is_none()));
+    let untouched = TestModel::get(&conn, TestModelRequest { id: Some(Filter::Eq(3)), ..Default::default() })?;
+    assert_eq!(untouched.test_val, "old");
+
+    let empty_patch = TestModel::update_where(&conn, TestModelRequest::default(), TestModelPatch::default());
+    assert!(empty_patch.is_err());
+
+    return Ok(());
+}
+
+#[test]
+fn update_partial() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'comment', 'old'), (2, 'comment', 'old');", [])?;
+
+    let updated = TestModel::update_partial(&conn, 1, TestModelPatch { test_val: Some(String::from("new")), ..Default::default() })?;
+    assert_eq!(updated, 1);
+
+    let changed = TestModel::get(&conn, TestModelRequest { id: Some(Filter::Eq(1)), ..Default::default() })?;
+    assert_eq!(changed.test_val, "new");
+    assert_eq!(changed.comments, Some(String::from("comment")));
+    let untouched = TestModel::get(&conn, TestModelRequest { id: Some(Filter::Eq(2)), ..Default::default() })?;
+    assert_eq!(untouched.test_val, "old");
+
+    assert!(TestModel::update_partial(&conn, 1, TestModelPatch::default()).is_err());
+
+    CompositeModel::create_table(&conn)?;
+    conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 1, x'00', NULL), (1, 2, x'00', NULL);", [])?;
+    let updated = CompositeModel::update_partial(&conn, (1, 2), CompositeModelPatch { score: Some(Some(1.5)), ..Default::default() })?;
+    assert_eq!(updated, 1);
+    let changed = CompositeModel::get(&conn, CompositeModelRequest { id1: Some(Filter::Eq(1)), id2: Some(Filter::Eq(2)), ..Default::default() })?;
+    assert_eq!(changed.score, Some(1.5));
+    assert_eq!(changed.blob, vec![0]);
+
+    return Ok(());
+}
+
+#[test]
+fn filter_operators() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'a', 'apple'), (2, NULL, 'banana'), (3, 'c', 'apricot'), (4, NULL, 'cherry');", [])?;
+
+    let ids = |request: TestModelRequest| -> anyhow::Result<Vec<usize>> {
+        let mut ids: Vec<usize> = TestModel::get_many(&conn, request)?.iter().map(|model| model.id).collect();
+        ids.sort();
+        return Ok(ids);
+    };
+
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::Ne(1)), ..Default::default() })?, vec![2, 3, 4]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::Lt(2)), ..Default::default() })?, vec![1]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::Le(2)), ..Default::default() })?, vec![1, 2]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::Gt(3)), ..Default::default() })?, vec![4]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::Ge(3)), ..Default::default() })?, vec![3, 4]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::Between(2, 3)), ..Default::default() })?, vec![2, 3]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::In(vec![1, 4])), ..Default::default() })?, vec![1, 4]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::In(vec![])), ..Default::default() })?, Vec::<usize>::new());
+    assert_eq!(ids(TestModelRequest { test_val: Some(Filter::Like(String::from("ap%"))), ..Default::default() })?, vec![1, 3]);
+    assert_eq!(ids(TestModelRequest { test_val: Some(Filter::Glob(String::from("*rr*"))), ..Default::default() })?, vec![4]);
+    assert_eq!(ids(TestModelRequest { comments: Some(Filter::IsNull), ..Default::default() })?, vec![2, 4]);
+    assert_eq!(ids(TestModelRequest { comments: Some(Filter::IsNotNull), ..Default::default() })?, vec![1, 3]);
+    assert_eq!(ids(TestModelRequest { id: Some(Filter::Gt(1)), comments: Some(Filter::IsNotNull), ..Default::default() })?, vec![3]);
+
+    return Ok(());
+}
+
+#[test]
+fn filter_combinators() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'a', 'apple'), (2, NULL, 'banana'), (3, 'c', 'apricot'), (4, NULL, 'cherry');", [])?;
+
+    let ids = |filter: TestModelFilter| -> anyhow::Result<Vec<usize>> {
+        let mut ids: Vec<usize> = TestModel::get_many(&conn, filter)?.iter().map(|model| model.id).collect();
+        ids.sort();
+        return Ok(ids);
+    };
+
+    let first = TestModelRequest { id: Some(Filter::Eq(1)), ..Default::default() };
+    let cherry = TestModelRequest { test_val: Some(Filter::Eq(String::from("cherry"))), ..Default::default() };
+    assert_eq!(ids(first.clone().or(cherry.clone()))?, vec![1, 4]);
+    assert_eq!(ids(!first.clone())?, vec![2, 3, 4]);
+    assert_eq!(ids(first.clone().and(cherry.clone()))?, Vec::<usize>::new());
+
+    let nulls = TestModelRequest { comments: Some(Filter::IsNull), ..Default::default() };
+    let nested = TestModelFilter::Or(vec![
+        first.clone().into(),
+        TestModelFilter::And(vec![nulls.into(), !cherry.clone()]),
+    ]);
+    assert_eq!(ids(nested)?, vec![1, 2]);
+    assert_eq!(ids(TestModelFilter::Or(vec![]))?, Vec::<usize>::new());
+    assert_eq!(ids(TestModelFilter::And(vec![]))?, vec![1, 2, 3, 4]);
+
+    let updated = TestModel::update_where(&conn, !TestModelRequest { comments: Some(Filter::IsNull), ..Default::default() }, TestModelPatch { test_val: Some(String::from("commented")), ..Default::default() })?;
+    assert_eq!(updated, 2);
+    assert_eq!(ids(TestModelRequest { test_val: Some(Filter::Eq(String::from("commented"))), ..Default::default() }.into())?, vec![1, 3]);
+
+    let deleted = TestModel::delete_where(&conn, first.or(cherry))?;
+    assert_eq!(deleted, 2);
+    assert_eq!(ids(TestModelRequest::default().into())?, vec![2, 3]);
+
+    return Ok(());
+}
+
+#[test]
+fn get_many_with_options() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, 'b', 'x'), (2, NULL, 'y'), (3, 'a', 'x'), (4, NULL, 'y');", [])?;
+
+    let ids = |options: QueryOptions<TestModelField>| -> anyhow::Result<Vec<usize>> {
+        return Ok(TestModel::get_many_with(&conn, TestModelRequest::default(), options)?.iter().map(|model| model.id).collect());
+    };
+
+    assert_eq!(ids(QueryOptions::new().desc(TestModelField::Id))?, vec![4, 3, 2, 1]);
+    assert_eq!(ids(QueryOptions::new().asc(TestModelField::Comments).asc(TestModelField::Id))?, vec![2, 4, 3, 1]);
+    assert_eq!(ids(QueryOptions::new().asc(TestModelField::Comments).nulls_last().asc(TestModelField::Id))?, vec![3, 1, 2, 4]);
+    assert_eq!(ids(QueryOptions::new().desc(TestModelField::Comments).nulls_first().desc(TestModelField::Id))?, vec![4, 2, 1, 3]);
+    assert_eq!(ids(QueryOptions::new().asc(TestModelField::TestVal).desc(TestModelField::Id).limit(3))?, vec![3, 1, 4]);
+    assert_eq!(ids(QueryOptions::new().asc(TestModelField::Id).limit(2).offset(1))?, vec![2, 3]);
+    assert_eq!(ids(QueryOptions::new().asc(TestModelField::Id).offset(3))?, vec![4]);
+
+    let filtered = TestModel::get_many_with(&conn, TestModelRequest { test_val: Some(Filter::Eq(String::from("y"))), ..Default::default() }, QueryOptions::new().order_by(TestModelField::Id, Direction::Desc).limit(1))?;
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].id, 4);
+
+    return Ok(());
+}
+
+#[test]
+fn page_after() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (5, NULL, 'b'), (2, NULL, 'a'), (4, NULL, 'b'), (1, NULL, 'c'), (3, NULL, 'a');", [])?;
+
+    let first = TestModel::page_after(&conn, TestModelRequest::default(), None, 2)?;
+    assert_eq!(first.rows.iter().map(|model| model.id).collect::<Vec<usize>>(), vec![1, 2]);
+    let second = TestModel::page_after(&conn, TestModelRequest::default(), first.next_cursor.as_ref(), 2)?;
+    assert_eq!(second.rows.iter().map(|model| model.id).collect::<Vec<usize>>(), vec![3, 4]);
+    let last = TestModel::page_after(&conn, TestModelRequest::default(), second.next_cursor.as_ref(), 2)?;
+    assert_eq!(last.rows.iter().map(|model| model.id).collect::<Vec<usize>>(), vec![5]);
+    assert!(last.next_cursor.is_none());
+
+    let mut ids: Vec<usize> = Vec::new();
+    let mut cursor: Option<Cursor> = None;
+    loop {
+        let page = TestModel::page_after_by(&conn, TestModelRequest { id: Some(Filter::Ne(1)), ..Default::default() }, &[TestModelField::TestVal], cursor.as_ref(), 1)?;
+        ids.extend(page.rows.iter().map(|model| model.id));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ids, vec![2, 3, 4, 5]);
+
+    // NULLs sort first and the cursor carries on past them.
+    conn.execute("UPDATE TestModel SET comments = 'y' WHERE id = 2;", [])?;
+    conn.execute("UPDATE TestModel SET comments = 'x' WHERE id = 4;", [])?;
+    let mut ids: Vec<usize> = Vec::new();
+    let mut cursor: Option<Cursor> = None;
+    loop {
+        let page = TestModel::page_after_by(&conn, TestModelRequest::default(), &[TestModelField::Comments], cursor.as_ref(), 1)?;
+        ids.extend(page.rows.iter().map(|model| model.id));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ids, vec![1, 3, 5, 4, 2]);
+
+    let token = first.next_cursor.unwrap().to_string();
+    let resumed = TestModel::page_after(&conn, TestModelRequest::default(), Some(&Cursor::from(token.as_str())), 10)?;
+    assert_eq!(resumed.rows.len(), 3);
+
+    assert!(TestModel::page_after(&conn, TestModelRequest::default(), Some(&Cursor::from("not a cursor")), 2).is_err());
+    // Cursors come back from clients, malformed ones are an error rather than a panic.
+    for bad in ["\u{e9}", "i\u{e9}", &token[..token.len() - 1]] {
+        let page = TestModel::page_after(&conn, TestModelRequest::default(), Some(&Cursor::from(bad)), 2);
+        assert!(matches!(page, Err(sqlite_helper::Error::InvalidCursor)));
+    }
+    assert!(TestModel::page_after(&conn, TestModelRequest::default(), None, 0).is_err());
+
+    CompositeModel::create_table(&conn)?;
+    conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 2, x'00', NULL), (2, 1, x'00', NULL), (1, 1, x'00', NULL);", [])?;
+    let composite = CompositeModel::page_after(&conn, CompositeModelRequest::default(), None, 2)?;
+    assert_eq!(composite.rows.iter().map(|model| (model.id1, model.id2)).collect::<Vec<(i64, i64)>>(), vec![(1, 1), (1, 2)]);
+    let composite = CompositeModel::page_after(&conn, CompositeModelRequest::default(), composite.next_cursor.as_ref(), 2)?;
+    assert_eq!(composite.rows.iter().map(|model| (model.id1, model.id2)).collect::<Vec<(i64, i64)>>(), vec![(2, 1)]);
+
+    return Ok(());
+}
+
+#[test]
+fn count_exists_and_aggregates() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    CompositeModel::create_table(&conn)?;
+    conn.execute("INSERT INTO CompositeModel (id1, id2, blob, score) VALUES (1, 1, x'00', 1.5), (1, 2, x'00', NULL), (2, 1, x'00', 4.5), (3, 1, x'00', 3.0);", [])?;
+
+    assert_eq!(CompositeModel::count(&conn, CompositeModelRequest::default())?, 4);
+    assert_eq!(CompositeModel::count(&conn, CompositeModelRequest { id1: Some(Filter::Eq(1)), ..Default::default() })?, 2);
+    assert!(CompositeModel::exists(&conn, CompositeModelRequest { id2: Some(Filter::Eq(2)), ..Default::default() })?);
+    assert!(!CompositeModel::exists(&conn, CompositeModelRequest { id1: Some(Filter::Gt(3)), ..Default::default() })?);
+
+    assert_eq!(CompositeModel::sum_id1(&conn, CompositeModelRequest::default())?, Some(7));
+    assert_eq!(CompositeModel::min_id1(&conn, CompositeModelRequest { id2: Some(Filter::Eq(1)), ..Default::default() })?, Some(1));
+    assert_eq!(CompositeModel::max_id1(&conn, CompositeModelRequest::default())?, Some(3));
+    assert_eq!(CompositeModel::avg_id2(&conn, CompositeModelRequest::default())?, Some(1.25));
+    assert_eq!(CompositeModel::sum_score(&conn, CompositeModelRequest::default())?, Some(9.0));
+    assert_eq!(CompositeModel::min_score(&conn, CompositeModelRequest::default())?, Some(1.5));
+    assert_eq!(CompositeModel::max_score(&conn, CompositeModelRequest { id1: Some(Filter::Eq(1)), ..Default::default() })?, Some(1.5));
+    assert_eq!(CompositeModel::avg_score(&conn, CompositeModelRequest::default())?, Some(3.0));
+
+    let nothing = CompositeModelRequest { id1: Some(Filter::Gt(3)), ..Default::default() };
+    assert_eq!(CompositeModel::count(&conn, nothing.clone())?, 0);
+    assert_eq!(CompositeModel::sum_score(&conn, nothing.clone())?, None);
+    assert_eq!(CompositeModel::max_id2(&conn, nothing)?, None);
+
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, NULL, 'a'), (2, NULL, 'b');", [])?;
+    let max_id: Option<usize> = TestModel::max_id(&conn, TestModelRequest::default())?;
+    assert_eq!(max_id, Some(2));
+
+    return Ok(());
+}
+
+#[test]
+fn for_each_and_row_errors() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (1, NULL, 'a'), (2, NULL, 'b'), (3, NULL, 'c');", [])?;
+
+    let mut ids: Vec<usize> = Vec::new();
+    TestModel::for_each(&conn, TestModelRequest::default(), |model| -> sqlite_helper::Result<()> {
+        ids.push(model?.id);
+        return Ok(());
+    })?;
+    ids.sort();
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    let mut ids: Vec<usize> = Vec::new();
+    TestModel::for_each_with(&conn, TestModelRequest::default(), QueryOptions::new().desc(TestModelField::Id).limit(2), |model| -> sqlite_helper::Result<()> {
+        ids.push(model?.id);
+        return Ok(());
+    })?;
+    assert_eq!(ids, vec![3, 2]);
+
+    let mut seen = 0;
+    let stopped = TestModel::for_each(&conn, TestModelRequest::default(), |_| -> anyhow::Result<()> {
+        seen += 1;
+        return Err(anyhow::anyhow!("stop"));
+    });
+    assert!(stopped.is_err());
+    assert_eq!(seen, 1);
+
+    // A blob can't be read into the String field.
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (4, NULL, x'00');", [])?;
+    assert!(TestModel::get_many(&conn, TestModelRequest::default()).is_err());
+
+    let mut errors = 0;
+    let mut rows = 0;
+    TestModel::for_each(&conn, TestModelRequest::default(), |model| -> sqlite_helper::Result<()> {
+        match model {
+            Ok(_) => rows += 1,
+            Err(_) => errors += 1,
+        }
+        return Ok(());
+    })?;
+    assert_eq!((rows, errors), (3, 1));
+
+    return Ok(());
+}
+
+#[test]
+fn get_not_found_and_not_unique() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (3, NULL, 'same'), (1, NULL, 'same'), (2, NULL, 'other');", [])?;
+
+    let missing = TestModel::get(&conn, TestModelRequest { id: Some(Filter::Eq(4)), ..Default::default() }).unwrap_err();
+    assert!(matches!(missing, sqlite_helper::Error::NotFound));
+    let same = TestModelRequest { test_val: Some(Filter::Eq(String::from("same"))), ..Default::default() };
+    let duplicate = TestModel::get(&conn, same.clone()).unwrap_err();
+    assert!(matches!(duplicate, sqlite_helper::Error::NotUnique));
+
+    assert!(TestModel::get_optional(&conn, TestModelRequest { id: Some(Filter::Eq(4)), ..Default::default() })?.is_none());
+    assert_eq!(TestModel::get_optional(&conn, TestModelRequest { id: Some(Filter::Eq(2)), ..Default::default() })?.map(|model| model.id), Some(2));
+    assert!(TestModel::get_optional(&conn, same.clone()).is_err());
+
+    assert_eq!(TestModel::get_first(&conn, same)?.id, 1);
+    let missing = TestModel::get_first(&conn, TestModelRequest { id: Some(Filter::Gt(3)), ..Default::default() }).unwrap_err();
+    assert!(matches!(missing, sqlite_helper::Error::NotFound));
+
+    return Ok(());
+}
+
+#[test]
+fn constraint_and_conversion_errors() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+    conn.execute("CREATE UNIQUE INDEX test_val_index ON TestModel (test_val);", [])?;
+    TestModel { id: 1, comments: None, test_val: String::from("a") }.add(&conn)?;
+
+    match (TestModel { id: 1, comments: None, test_val: String::from("b") }).add(&conn).unwrap_err() {
+        sqlite_helper::Error::Constraint { kind, table, columns, .. } => {
+            assert_eq!(kind, sqlite_helper::ConstraintKind::PrimaryKey);
+            assert_eq!(table.as_deref(), Some("TestModel"));
+            assert_eq!(columns, vec!["id"]);
+        },
+        err => panic!("expected a constraint error, got {:?}", err),
+    }
+    match (TestModel { id: 2, comments: None, test_val: String::from("a") }).add(&conn).unwrap_err() {
+        sqlite_helper::Error::Constraint { kind, columns, .. } => {
+            assert_eq!(kind, sqlite_helper::ConstraintKind::Unique);
+            assert_eq!(columns, vec!["test_val"]);
+        },
+        err => panic!("expected a constraint error, got {:?}", err),
+    }
+
+    // The struct doesn't know about the email column, so add leaves it NULL.
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, createdAt INTEGER NOT NULL, email TEXT NOT NULL);", [])?;
+    match (User { id: 1, name: String::from("first"), created_at: 10 }).add(&conn).unwrap_err() {
+        sqlite_helper::Error::Constraint { kind, table, columns, .. } => {
+            assert_eq!(kind, sqlite_helper::ConstraintKind::NotNull);
+            assert_eq!(table.as_deref(), Some("users"));
+            assert_eq!(columns, vec!["email"]);
+        },
+        err => panic!("expected a constraint error, got {:?}", err),
+    }
+
+    conn.execute("INSERT INTO TestModel (id, comments, test_val) VALUES (3, NULL, x'00');", [])?;
+    assert!(matches!(TestModel::get_many(&conn, TestModelRequest::default()).unwrap_err(), sqlite_helper::Error::Conversion(_)));
+
+    return Ok(());
+}
+
+#[test]
+fn transaction_and_savepoint() -> anyhow::Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+
+    let tx = conn.transaction()?;
+    TestModel { id: 1, comments: None, test_val: String::from("a") }.add(&tx)?;
+    TestModel { id: 2, comments: None, test_val: String::from("b") }.add(&tx)?;
+    assert_eq!(TestModel::count(&tx, TestModelRequest::default())?, 2);
+    tx.rollback()?;
+    assert_eq!(TestModel::count(&conn, TestModelRequest::default())?, 0);
+
+    let mut tx = conn.transaction()?;
+    TestModel { id: 1, comments: None, test_val: String::from("a") }.add(&tx)?;
+    {
+        // Dropping a savepoint rolls it back.
+        let savepoint = tx.savepoint()?;
+        TestModel { id: 2, comments: None, test_val: String::from("b") }.add(&savepoint)?;
+        assert!(TestModel::exists(&savepoint, TestModelRequest { id: Some(Filter::Eq(2)), ..Default::default() })?);
+    }
+    tx.commit()?;
+
+    let ids: Vec<usize> = TestModel::get_many(&conn, TestModelRequest::default())?.into_iter().map(|model| model.id).collect();
+    assert_eq!(ids, vec![1]);
+
+    return Ok(());
+}
+
+#[derive(Queryable, Debug)]
+struct IdOnlyModel {
+    #[primary(autoincrement)]
+    pub id: i64
+}
+
+#[test]
+fn add_many() -> anyhow::Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+
+    // 3 columns a row, more rows than fit under the parameter limit in one statement.
+    let models: Vec<TestModel> = (1..=1000).map(|id| TestModel { id, comments: None, test_val: id.to_string() }).collect();
+    assert_eq!(TestModel::add_many(&conn, &models)?, 1000);
+    assert_eq!(TestModel::count(&conn, TestModelRequest::default())?, 1000);
+    assert_eq!(TestModel::get(&conn, TestModelRequest { id: Some(Filter::Eq(734)), ..Default::default() })?.test_val, "734");
+    assert_eq!(TestModel::add_many(&conn, &[])?, 0);
+
+    // The duplicate in the last chunk undoes the rows added before it.
+    let models: Vec<TestModel> = (1001..=1400).chain(1..=1).map(|id| TestModel { id, comments: None, test_val: id.to_string() }).collect();
+    assert!(matches!(TestModel::add_many(&conn, &models), Err(sqlite_helper::Error::Constraint { .. })));
+    assert_eq!(TestModel::count(&conn, TestModelRequest::default())?, 1000);
+
+    let tx = conn.transaction()?;
+    TestModel::add_many(&tx, &[TestModel { id: 2000, comments: None, test_val: String::from("a") }])?;
+    tx.rollback()?;
+    assert_eq!(TestModel::count(&conn, TestModelRequest::default())?, 1000);
+
+    IdOnlyModel::create_table(&conn)?;
+    assert_eq!(IdOnlyModel::add_many(&conn, &[IdOnlyModel { id: 0 }, IdOnlyModel { id: 0 }])?, 2);
+    let ids: Vec<i64> = IdOnlyModel::get_many(&conn, IdOnlyModelRequest::default())?.into_iter().map(|model| model.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+
+    return Ok(());
+}
+
+#[derive(Queryable, Debug)]
+#[table(name = "accounts")]
+struct Account {
+    #[primary(autoincrement)]
+    pub id: i64,
+    #[unique]
+    pub email: String,
+    pub name: String
+}
+
+#[derive(Queryable, Debug)]
+struct Member {
+    #[primary]
+    pub id: i64,
+    #[unique]
+    pub email: String,
+    pub name: String
+}
+
+#[test]
+fn upsert_and_insert_or() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    initialize_table(&conn)?;
+
+    assert_eq!(TestModel { id: 1, comments: None, test_val: String::from("a") }.upsert(&conn)?, 1);
+    assert_eq!(TestModel { id: 1, comments: Some(String::from("updated")), test_val: String::from("b") }.upsert(&conn)?, 1);
+    let model = TestModel::get(&conn, TestModelRequest::default())?;
+    assert_eq!((model.comments.as_deref(), model.test_val.as_str()), (Some("updated"), "b"));
+
+    assert_eq!(TestModel { id: 1, comments: None, test_val: String::from("c") }.insert_or_ignore(&conn)?, 0);
+    assert_eq!(TestModel::get(&conn, TestModelRequest::default())?.test_val, "b");
+    assert_eq!(TestModel { id: 1, comments: None, test_val: String::from("c") }.insert_or_replace(&conn)?, 1);
+    assert_eq!(TestModel::get(&conn, TestModelRequest::default())?.test_val, "c");
+
+    assert_eq!(Account::create_table_sql(), r#"CREATE TABLE "accounts" ("id" INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, "email" TEXT NOT NULL UNIQUE, "name" TEXT NOT NULL);"#);
+    Account::create_table(&conn)?;
+    Account { id: 0, email: String::from("a@example.com"), name: String::from("first") }.upsert(&conn)?;
+    Account { id: 0, email: String::from("a@example.com"), name: String::from("second") }.upsert(&conn)?;
+    let account = Account::get(&conn, AccountRequest::default())?;
+    assert_eq!((account.id, account.name.as_str()), (1, "second"));
+
+    // Replacing deletes the conflicting row, so SQLite picks a new id.
+    Account { id: 0, email: String::from("a@example.com"), name: String::from("third") }.insert_or_replace(&conn)?;
+    let account = Account::get(&conn, AccountRequest::default())?;
+    assert_ne!(account.id, 1);
+    assert_eq!(account.name, "third");
+
+    // Matching on the unique email leaves the existing row's primary key alone.
+    Member::create_table(&conn)?;
+    Member { id: 1, email: String::from("a@example.com"), name: String::from("first") }.upsert(&conn)?;
+    assert_eq!(Member { id: 99, email: String::from("a@example.com"), name: String::from("second") }.upsert(&conn)?, 1);
+    let member = Member::get(&conn, MemberRequest::default())?;
+    assert_eq!((member.id, member.name.as_str()), (1, "second"));
+
+    // A plain autoincrement id is a placeholder, so every upsert adds a row with an id SQLite picks.
+    AutoIncrementModel::create_table(&conn)?;
+    AutoIncrementModel { id: 0, name: String::from("a") }.upsert(&conn)?;
+    AutoIncrementModel { id: 0, name: String::from("b") }.upsert(&conn)?;
+    let ids: Vec<usize> = AutoIncrementModel::get_many(&conn, AutoIncrementModelRequest::default())?.into_iter().map(|model| model.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+
+    // An Option id is the conflict target when it is Some, and left to SQLite when it is None.
+    OptionalIdModel::create_table(&conn)?;
+    OptionalIdModel { id: None, name: String::from("a") }.upsert(&conn)?;
+    assert_eq!(OptionalIdModel { id: Some(1), name: String::from("b") }.upsert(&conn)?, 1);
+    let model = OptionalIdModel::get(&conn, OptionalIdModelRequest::default())?;
+    assert_eq!((model.id, model.name.as_str()), (Some(1), "b"));
+
+    assert_eq!(OptionalIdModel { id: Some(1), name: String::from("c") }.insert_or_ignore(&conn)?, 0);
+    assert_eq!(OptionalIdModel { id: Some(1), name: String::from("c") }.insert_or_replace(&conn)?, 1);
+    OptionalIdModel { id: None, name: String::from("d") }.insert_or_replace(&conn)?;
+    let models: Vec<(Option<i64>, String)> = OptionalIdModel::get_many(&conn, OptionalIdModelRequest::default())?.into_iter().map(|model| (model.id, model.name)).collect();
+    assert_eq!(models, vec![(Some(1), String::from("c")), (Some(2), String::from("d"))]);
+
+    return Ok(());
+}
+
+#[test]
+fn returning() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    Account::create_table(&conn)?;
+
+    let account = Account { id: 0, email: String::from("a@example.com"), name: String::from("first") }.add_returning(&conn)?;
+    assert_eq!((account.id, account.name.as_str()), (1, "first"));
+    let accounts = Account::add_many_returning(&conn, &[
+        Account { id: 0, email: String::from("b@example.com"), name: String::from("second") },
+        Account { id: 0, email: String::from("c@example.com"), name: String::from("third") },
+    ])?;
+    let mut ids: Vec<i64> = accounts.iter().map(|account| account.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![2, 3]);
+
+    let updated = Account { id: 1, email: String::from("a@example.com"), name: String::from("renamed") }.update_returning(&conn)?;
+    assert_eq!(updated.name, "renamed");
+    let missing = Account { id: 10, email: String::from("z@example.com"), name: String::from("missing") };
+    assert!(matches!(missing.update_returning(&conn), Err(sqlite_helper::Error::NotFound)));
+
+    let patch = AccountPatch { name: Some(String::from("bulk")), ..Default::default() };
+    let updated = Account::update_where_returning(&conn, AccountRequest { id: Some(Filter::Gt(1)), ..Default::default() }, patch)?;
+    assert_eq!(updated.len(), 2);
+    assert!(updated.iter().all(|account| account.name == "bulk"));
+
+    let deleted = Account { id: 1, email: String::new(), name: String::new() }.delete_returning(&conn)?;
+    assert_eq!((deleted.email.as_str(), deleted.name.as_str()), ("a@example.com", "renamed"));
+    assert!(matches!(deleted.delete_returning(&conn), Err(sqlite_helper::Error::NotFound)));
+    assert_eq!(Account::delete_where_returning(&conn, AccountRequest::default())?.len(), 2);
+    assert_eq!(Account::count(&conn, AccountRequest::default())?, 0);
+
+    // Renamed columns come back under their field names.
+    User::create_table(&conn)?;
+    let user = User { id: 1, name: String::from("first"), created_at: 10 }.add_returning(&conn)?;
+    assert_eq!(user.created_at, 10);
+
+    return Ok(());
+}
+
+#[derive(Queryable, Debug)]
+struct Link {
+    #[primary]
+    pub a: i64,
+    #[primary]
+    pub b: i64
+}
+
+#[test]
+fn key_only_struct() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+    assert_eq!(Link::create_table_sql(), r#"CREATE TABLE "Link" ("a" INTEGER NOT NULL, "b" INTEGER NOT NULL, PRIMARY KEY("a", "b"));"#);
+    Link::create_table(&conn)?;
+
+    // No `update` is generated, every column is part of the key.
+    Link { a: 1, b: 2 }.add(&conn)?;
+    Link { a: 1, b: 3 }.add(&conn)?;
+    assert_eq!(Link { a: 1, b: 2 }.upsert(&conn)?, 0);
+    assert_eq!(Link::update_partial(&conn, (1, 3), LinkPatch { b: Some(4), ..Default::default() })?, 1);
+    assert_eq!(Link::get(&conn, LinkRequest { b: Some(Filter::Eq(4)), ..Default::default() })?.a, 1);
+
+    assert_eq!(Link { a: 1, b: 2 }.delete(&conn)?, 1);
+    assert_eq!(Link::count(&conn, LinkRequest::default())?, 1);
+
+    return Ok(());
+}
+
+#[derive(Queryable, Debug)]
+struct Profile {
+    #[primary]
+    pub id: i64,
+    #[unique]
+    pub email: String,
+    #[unique]
+    pub username: String
+}
+
+#[derive(Queryable, Debug)]
+#[table(unique(owner, slug))]
+struct Project {
+    #[primary(autoincrement)]
+    pub id: i64,
+    pub owner: String,
+    pub slug: String,
+    pub title: String
+}
+
+#[test]
+fn unique_constraints() -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open_in_memory()?;
+
+    // Each #[unique] field is a constraint of its own.
+    Profile::create_table(&conn)?;
+    Profile { id: 1, email: String::from("a@example.com"), username: String::from("a") }.add(&conn)?;
+    let duplicate_email = Profile { id: 2, email: String::from("a@example.com"), username: String::from("b") }.add(&conn);
+    assert!(matches!(duplicate_email, Err(sqlite_helper::Error::Constraint { kind: sqlite_helper::ConstraintKind::Unique, .. })));
+    let duplicate_username = Profile { id: 2, email: String::from("b@example.com"), username: String::from("a") }.add(&conn);
+    assert!(matches!(duplicate_username, Err(sqlite_helper::Error::Constraint { kind: sqlite_helper::ConstraintKind::Unique, .. })));
+
+    // With several #[unique] fields upsert matches on the primary key.
+    assert_eq!(Profile { id: 1, email: String::from("c@example.com"), username: String::from("c") }.upsert(&conn)?, 1);
+    assert_eq!(Profile::get(&conn, ProfileRequest::default())?.email, "c@example.com");
+
+    // #[table(unique(...))] makes one constraint over its fields, which upsert matches on.
+    assert_eq!(Project::create_table_sql(), r#"CREATE TABLE "Project" ("id" INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, "owner" TEXT NOT NULL, "slug" TEXT NOT NULL, "title" TEXT NOT NULL, UNIQUE("owner", "slug"));"#);
+    Project::create_table(&conn)?;
+    Project { id: 0, owner: String::from("a"), slug: String::from("site"), title: String::from("first") }.add(&conn)?;
+    Project { id: 0, owner: String::from("b"), slug: String::from("site"), title: String::from("other") }.add(&conn)?;
+    Project { id: 0, owner: String::from("a"), slug: String::from("site"), title: String::from("second") }.upsert(&conn)?;
+    let project = Project::get(&conn, ProjectRequest { owner: Some(Filter::Eq(String::from("a"))), ..Default::default() })?;
+    assert_eq!((project.id, project.title.as_str()), (1, "second"));
+    assert_eq!(Project::count(&conn, ProjectRequest::default())?, 2);
+
+    return Ok(());
+}
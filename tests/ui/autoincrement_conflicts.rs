@@ -0,0 +1,25 @@
+use database_macros::Queryable;
+
+#[derive(Queryable)]
+struct TextAutoincrement {
+    #[primary(autoincrement)]
+    id: String,
+}
+
+#[derive(Queryable)]
+struct CompositeAutoincrement {
+    #[primary(autoincrement)]
+    id: i64,
+    #[primary]
+    version: i64,
+}
+
+#[derive(Queryable)]
+struct TwoAutoincrements {
+    #[primary(autoincrement)]
+    id: i64,
+    #[primary(autoincrement)]
+    other_id: i64,
+}
+
+fn main() {}
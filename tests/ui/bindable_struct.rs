@@ -0,0 +1,8 @@
+use database_macros::Bindable;
+
+#[derive(Bindable)]
+struct NotAnEnum {
+    value: String,
+}
+
+fn main() {}
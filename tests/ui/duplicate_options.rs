@@ -0,0 +1,32 @@
+use database_macros::Queryable;
+
+#[derive(Queryable)]
+#[table(name = "users", name = "people")]
+struct DuplicateTableName {
+    #[primary]
+    id: i64,
+}
+
+#[derive(Queryable)]
+#[table(debug, debug)]
+struct DuplicateDebug {
+    #[primary]
+    id: i64,
+}
+
+#[derive(Queryable)]
+struct DuplicatePrimary {
+    #[primary]
+    #[primary(autoincrement)]
+    id: i64,
+}
+
+#[derive(Queryable)]
+struct DuplicateColumn {
+    #[primary]
+    id: i64,
+    #[column(name = "id")]
+    other_id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use database_macros::Queryable;
+
+#[derive(Queryable)]
+struct NumericVariant {
+    #[primary]
+    id: i64,
+    _1: String,
+}
+
+#[derive(Queryable)]
+struct KeywordVariant {
+    #[primary]
+    id: i64,
+    self_: String,
+}
+
+#[derive(Queryable)]
+struct DuplicateVariant {
+    #[primary]
+    id: i64,
+    a_b: String,
+    a__b: String,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use database_macros::Queryable;
+
+#[derive(Queryable)]
+struct EmptyColumnName {
+    #[primary]
+    id: i64,
+    #[column(name = "")]
+    name: String,
+}
+
+#[derive(Queryable)]
+#[table(name = "")]
+struct EmptyTableName {
+    #[primary]
+    id: i64,
+}
+
+#[derive(Queryable)]
+struct NoFields {}
+
+fn main() {}
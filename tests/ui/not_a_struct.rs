@@ -0,0 +1,9 @@
+use database_macros::Queryable;
+
+#[derive(Queryable)]
+enum NotAStruct {
+    First,
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use database_macros::LibSqlQueryable;
+
+#[derive(LibSqlQueryable)]
+struct TupleStruct(i64, String);
+
+fn main() {}
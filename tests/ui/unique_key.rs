@@ -0,0 +1,34 @@
+use database_macros::Queryable;
+
+#[derive(Queryable)]
+#[table(unique(email, username))]
+struct UnknownUniqueField {
+    #[primary]
+    id: i64,
+    email: String,
+}
+
+#[derive(Queryable)]
+#[table(unique(email, email))]
+struct RepeatedUniqueField {
+    #[primary]
+    id: i64,
+    email: String,
+}
+
+#[derive(Queryable)]
+#[table(unique())]
+struct EmptyUniqueKey {
+    #[primary]
+    id: i64,
+}
+
+#[derive(Queryable)]
+#[table(unique(email), unique(id))]
+struct DuplicateUniqueKey {
+    #[primary]
+    id: i64,
+    email: String,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use database_macros::Queryable;
+
+#[derive(Queryable)]
+#[table(table_name = "users")]
+struct UnknownTableOption {
+    #[primary]
+    id: i64,
+}
+
+#[derive(Queryable)]
+#[table(rename_all = "Title Case")]
+struct UnknownRenameRule {
+    #[primary]
+    id: i64,
+}
+
+#[derive(Queryable)]
+struct UnknownFieldOptions {
+    #[primary(auto_increment)]
+    id: i64,
+    #[column(rename = "display")]
+    name: String,
+    #[unique(email)]
+    email: String,
+}
+
+fn main() {}